@@ -0,0 +1,267 @@
+use std::path::PathBuf;
+
+use aoc_2024::{input::InputSource, solution::Part};
+
+pub(crate) const USAGE: &str = "Usage:
+    aoc-2024 run --day <N> [--part <1|2>] [--input <FILE|-> | --inputs <DIR>]
+    aoc-2024 run --all [--part <1|2>] [--inputs <DIR>]
+    aoc-2024 verify [--day <N>] [--answers <FILE>] [--input <FILE|-> | --inputs <DIR>]
+    aoc-2024 list
+    aoc-2024 help
+
+Without --input or --inputs the puzzle input embedded in the binary is used.
+--input - reads from stdin, --inputs reads <DIR>/day<N>.txt for each day.
+Without --answers, verify checks against the answers embedded in the binary.
+Set TIME_IT_SINK to stdout, stderr or jsonl:<FILE> to redirect function timings
+and TIME_IT_THRESHOLD_MS to only report calls slower than that. Timings are
+only collected when built with --features timing.";
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Command {
+    Run {
+        selection: Selection,
+        part: Option<Part>,
+        input: InputSource,
+    },
+    Verify {
+        selection: Selection,
+        answers: Option<PathBuf>,
+        input: InputSource,
+    },
+    List,
+    Help,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum Selection {
+    All,
+    Day(u8),
+}
+
+pub(crate) fn parse_args<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut args = args.into_iter();
+
+    match args.next().as_deref() {
+        Some("run") => parse_run(args),
+        Some("verify") => parse_verify(args),
+        Some("list") => Ok(Command::List),
+        Some("help") | Some("--help") | Some("-h") | None => Ok(Command::Help),
+        Some(other) => Err(format!("unknown command `{}`", other)),
+    }
+}
+
+fn parse_run<I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut selection: Option<Selection> = None;
+    let mut part: Option<Part> = None;
+    let mut input: Option<InputSource> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" | "-a" => set_selection(&mut selection, Selection::All)?,
+            "--day" | "-d" => {
+                let day = parse_value(&arg, args.next())?;
+                set_selection(&mut selection, Selection::Day(day))?
+            }
+            "--part" | "-p" => {
+                part = match parse_value::<u8>(&arg, args.next())? {
+                    1 => Some(Part::One),
+                    2 => Some(Part::Two),
+                    n => return Err(format!("part must be 1 or 2, got {}", n)),
+                }
+            }
+            "--input" | "-i" => {
+                let value: String = parse_value(&arg, args.next())?;
+                set_input(&mut input, InputSource::from_arg(&value))?
+            }
+            "--inputs" => {
+                let dir: PathBuf = parse_value(&arg, args.next())?;
+                set_input(&mut input, InputSource::Directory(dir))?
+            }
+            other => return Err(format!("unknown argument `{}`", other)),
+        }
+    }
+
+    let selection = selection.ok_or("`run` requires either --day <N> or --all")?;
+    let input = input.unwrap_or_default();
+
+    if selection == Selection::All && input.is_single() {
+        return Err("--input can only be used with --day, use --inputs <DIR> with --all".into());
+    }
+
+    Ok(Command::Run {
+        selection,
+        part,
+        input,
+    })
+}
+
+fn parse_verify<I>(mut args: I) -> Result<Command, String>
+where
+    I: Iterator<Item = String>,
+{
+    let mut day: Option<u8> = None;
+    let mut answers: Option<PathBuf> = None;
+    let mut input: Option<InputSource> = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => day = Some(parse_value(&arg, args.next())?),
+            "--answers" => answers = Some(parse_value(&arg, args.next())?),
+            "--input" | "-i" => {
+                let value: String = parse_value(&arg, args.next())?;
+                set_input(&mut input, InputSource::from_arg(&value))?
+            }
+            "--inputs" => {
+                let dir: PathBuf = parse_value(&arg, args.next())?;
+                set_input(&mut input, InputSource::Directory(dir))?
+            }
+            other => return Err(format!("unknown argument `{}`", other)),
+        }
+    }
+
+    let selection = day.map_or(Selection::All, Selection::Day);
+    let input = input.unwrap_or_default();
+
+    if selection == Selection::All && input.is_single() {
+        return Err("--input can only be used with --day, use --inputs <DIR> otherwise".into());
+    }
+
+    Ok(Command::Verify {
+        selection,
+        answers,
+        input,
+    })
+}
+
+fn set_selection(current: &mut Option<Selection>, next: Selection) -> Result<(), String> {
+    if current.is_some() {
+        return Err("--day and --all can only be given once".to_string());
+    }
+
+    *current = Some(next);
+
+    Ok(())
+}
+
+fn set_input(current: &mut Option<InputSource>, next: InputSource) -> Result<(), String> {
+    if current.is_some() {
+        return Err("--input and --inputs can only be given once".to_string());
+    }
+
+    *current = Some(next);
+
+    Ok(())
+}
+
+fn parse_value<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("{} expects a value", flag))?;
+
+    value
+        .parse()
+        .map_err(|_| format!("invalid value `{}` for {}", value, flag))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{parse_args, Command, Selection};
+    use aoc_2024::{input::InputSource, solution::Part};
+
+    fn args(input: &str) -> Vec<String> {
+        input.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn run_with_day_and_part() {
+        let expected = Command::Run {
+            selection: Selection::Day(6),
+            part: Some(Part::Two),
+            input: InputSource::Embedded,
+        };
+
+        assert_eq!(parse_args(args("run --day 6 --part 2")), Ok(expected));
+    }
+
+    #[test]
+    fn run_all() {
+        let expected = Command::Run {
+            selection: Selection::All,
+            part: None,
+            input: InputSource::Embedded,
+        };
+
+        assert_eq!(parse_args(args("run --all")), Ok(expected));
+    }
+
+    #[test]
+    fn list_and_help() {
+        assert_eq!(parse_args(args("list")), Ok(Command::List));
+        assert_eq!(parse_args(args("")), Ok(Command::Help));
+    }
+
+    #[test]
+    fn run_requires_a_selection() {
+        assert!(parse_args(args("run --part 1")).is_err());
+        assert!(parse_args(args("run --day 1 --all")).is_err());
+    }
+
+    #[test]
+    fn invalid_part_is_rejected() {
+        assert!(parse_args(args("run --day 1 --part 3")).is_err());
+        assert!(parse_args(args("run --day one")).is_err());
+    }
+
+    #[test]
+    fn run_with_input_sources() {
+        let expected = Command::Run {
+            selection: Selection::Day(3),
+            part: None,
+            input: InputSource::Stdin,
+        };
+
+        assert_eq!(parse_args(args("run --day 3 --input -")), Ok(expected));
+
+        let expected = Command::Run {
+            selection: Selection::All,
+            part: None,
+            input: InputSource::Directory(PathBuf::from("inputs")),
+        };
+
+        assert_eq!(parse_args(args("run --all --inputs inputs")), Ok(expected));
+    }
+
+    #[test]
+    fn single_input_is_rejected_for_all_days() {
+        assert!(parse_args(args("run --all --input day1.txt")).is_err());
+        assert!(parse_args(args("run --day 1 --input a.txt --inputs b")).is_err());
+    }
+
+    #[test]
+    fn verify_defaults_to_all_days() {
+        let expected = Command::Verify {
+            selection: Selection::All,
+            answers: None,
+            input: InputSource::Embedded,
+        };
+
+        assert_eq!(parse_args(args("verify")), Ok(expected));
+
+        let expected = Command::Verify {
+            selection: Selection::Day(2),
+            answers: Some(PathBuf::from("answers.txt")),
+            input: InputSource::Embedded,
+        };
+
+        assert_eq!(
+            parse_args(args("verify --day 2 --answers answers.txt")),
+            Ok(expected)
+        );
+    }
+}
@@ -1,18 +1,190 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    error::Error,
+    fmt::Display,
+    io::{self, BufRead},
+};
 
-const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
+use crate::{
+    parse::{list, ParseError},
+    solution::Solution,
+};
 
-pub(crate) fn run() {
-    println!("===== DAY ONE =====");
-    println!("Part 1: {:?}", part_one(PUZZLE_INPUT));
-    println!("Part 2: {:?}", part_two(PUZZLE_INPUT));
+pub const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
+
+pub struct DayOne;
+
+impl Solution for DayOne {
+    const DAY: u8 = 1;
+    const PUZZLE_INPUT: &'static str = PUZZLE_INPUT;
+
+    type Input = Columns;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let columns = Columns::read(input.as_bytes(), &[0, 1]).map_err(|err| match err {
+            ReadError::Parse(err) => err,
+            ReadError::Io(err) => unreachable!("reading from memory failed: {}", err),
+        })?;
+
+        if columns.width() < 2 {
+            let end = input.trim_end();
+
+            return Err(ParseError::at(
+                input,
+                &end[end.len()..],
+                "expected two columns",
+            ));
+        }
+
+        Ok(columns)
+    }
+
+    fn part_one(columns: &Self::Input) -> u64 {
+        distance(columns.column(0), columns.column(1))
+    }
+
+    fn part_two(columns: &Self::Input) -> u64 {
+        similarity(columns.column(0), columns.column(1))
+    }
+}
+
+/// Rows of whitespace separated numbers, keeping only some of the columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Columns {
+    width: usize,
+    columns: Vec<Vec<u64>>,
 }
 
-fn part_one(input: &str) -> u32 {
-    let (mut left, mut right): (Vec<u32>, Vec<u32>) = split_lists(input);
+impl Columns {
+    /// Reads one row per line, keeping the `selected` columns in that order.
+    /// The first row sets the number of columns, every other row has to have
+    /// as many. Blank lines are skipped.
+    pub fn read(mut reader: impl BufRead, selected: &[usize]) -> Result<Self, ReadError> {
+        let mut width = None;
+        let mut columns: Vec<Vec<u64>> = vec![Vec::new(); selected.len()];
+        let mut line = String::new();
+        let mut line_number = 0;
+
+        loop {
+            line.clear();
+            line_number += 1;
+
+            if reader.read_line(&mut line)? == 0 {
+                break;
+            }
+
+            let row = line.trim_end();
+
+            if row.trim().is_empty() {
+                continue;
+            }
+
+            let values = list::<u64>(row, row, " ").map_err(|err| err.on_line(line_number))?;
+            let expected = *width.get_or_insert(values.len());
 
-    left.sort();
-    right.sort();
+            if values.len() != expected {
+                let err = ParseError::at(
+                    row,
+                    row,
+                    format!("expected {} columns, found {}", expected, values.len()),
+                );
+
+                return Err(err.on_line(line_number).into());
+            }
+
+            for (column, index) in columns.iter_mut().zip(selected) {
+                let Some(value) = values.get(*index) else {
+                    let err = ParseError::at(
+                        row,
+                        &row[row.len()..],
+                        format!(
+                            "expected at least {} columns, found {}",
+                            index + 1,
+                            values.len()
+                        ),
+                    );
+
+                    return Err(err.on_line(line_number).into());
+                };
+
+                column.push(*value);
+            }
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            columns,
+        })
+    }
+
+    /// Number of columns in the input, selected or not.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of rows.
+    pub fn len(&self) -> usize {
+        self.columns.first().map_or(0, Vec::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The column selected at `index` when reading. Panics when fewer columns
+    /// were selected.
+    pub fn column(&self, index: usize) -> &[u64] {
+        &self.columns[index]
+    }
+}
+
+/// Why [`Columns::read`] failed.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl From<io::Error> for ReadError {
+    fn from(err: io::Error) -> Self {
+        ReadError::Io(err)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(err: ParseError) -> Self {
+        ReadError::Parse(err)
+    }
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReadError::Io(err) => write!(f, "failed to read input: {}", err),
+            ReadError::Parse(err) => write!(f, "{}", err),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ReadError::Io(err) => Some(err),
+            ReadError::Parse(err) => Some(err),
+        }
+    }
+}
+
+/// Sum of the differences between the smallest value of each column, then
+/// the second smallest and so on. Extra values in the longer column are
+/// ignored.
+pub fn distance(left: &[u64], right: &[u64]) -> u64 {
+    let mut left = left.to_vec();
+    let mut right = right.to_vec();
+
+    left.sort_unstable();
+    right.sort_unstable();
 
     left.iter()
         .zip(right.iter())
@@ -20,17 +192,12 @@ fn part_one(input: &str) -> u32 {
         .sum()
 }
 
-fn part_two(input: &str) -> u32 {
-    let (left, right) = split_lists(input);
-
-    let mut right_counts: HashMap<u32, u32> = HashMap::new();
+/// Sum of every value in `left` times the number of times it is in `right`.
+pub fn similarity(left: &[u64], right: &[u64]) -> u64 {
+    let mut right_counts: HashMap<u64, u64> = HashMap::new();
 
     for value in right {
-        if !right_counts.contains_key(&value) {
-            right_counts.insert(value, 0);
-        }
-
-        right_counts.insert(value, right_counts.get(&value).unwrap() + 1);
+        *right_counts.entry(*value).or_default() += 1;
     }
 
     left.iter()
@@ -38,43 +205,18 @@ fn part_two(input: &str) -> u32 {
         .sum()
 }
 
-fn split_lists(input: &str) -> (Vec<u32>, Vec<u32>) {
-    input
-        .lines()
-        .map(|line| {
-            let line = line.split_whitespace();
-
-            let left = line
-                .clone()
-                .take(1)
-                .collect::<Vec<&str>>()
-                .first()
-                .map(|l| l.parse::<u32>().expect("failed to parse left"))
-                .expect("failed to retrieve left hand side");
-            let right = line
-                .clone()
-                .skip(1)
-                .take(1)
-                .collect::<Vec<&str>>()
-                .first()
-                .map(|r| r.parse::<u32>().expect("failed to parse right"))
-                .expect("failed to retrieve right hand side");
-
-            (left, right)
-        })
-        .unzip()
-}
-
 #[cfg(test)]
 mod tests {
+    use super::{distance, similarity, Columns, DayOne, ReadError};
+    use crate::solution::Solution;
 
     const EXAMPLE_INPUT: &str = include_str!("./example_input.txt");
 
     #[test]
-    fn part_one__example_returns_the_correct_answer() {
+    fn part_one_example_returns_the_correct_answer() {
         let expected = 11;
 
-        let actual = super::part_one(EXAMPLE_INPUT);
+        let actual = DayOne::solve_part_one(EXAMPLE_INPUT);
 
         assert_eq!(actual, expected)
     }
@@ -83,8 +225,50 @@ mod tests {
     fn part_two_example_returns_the_correct_answer() {
         let expected = 31;
 
-        let actual = super::part_two(EXAMPLE_INPUT);
+        let actual = DayOne::solve_part_two(EXAMPLE_INPUT);
 
         assert_eq!(actual, expected)
     }
+
+    #[test]
+    fn reads_only_the_selected_columns() {
+        let columns = Columns::read("1 10 3\n\n2  20 3\n3 30 1\n".as_bytes(), &[2, 0]).unwrap();
+
+        assert_eq!(columns.width(), 3);
+        assert_eq!(columns.len(), 3);
+        assert_eq!(columns.column(0), [3, 3, 1]);
+        assert_eq!(columns.column(1), [1, 2, 3]);
+        assert_eq!(distance(columns.column(1), columns.column(0)), 1);
+        assert_eq!(similarity(columns.column(1), columns.column(0)), 7);
+    }
+
+    #[test]
+    fn missing_selected_columns_are_reported() {
+        let Err(ReadError::Parse(err)) = Columns::read("1 2\n3 4\n".as_bytes(), &[0, 2]) else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((err.line, err.column), (1, 4));
+        assert_eq!(err.message, "expected at least 3 columns, found 2");
+    }
+
+    #[test]
+    fn ragged_rows_are_reported_on_their_line() {
+        let Err(ReadError::Parse(err)) = Columns::read("1 2\n3 4\n5 6 7\n".as_bytes(), &[0, 1])
+        else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!(err.line, 3);
+        assert_eq!(err.message, "expected 2 columns, found 3");
+    }
+
+    #[test]
+    fn invalid_values_are_located() {
+        let Err(ReadError::Parse(err)) = Columns::read("1 2\n3 x4\n".as_bytes(), &[0, 1]) else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "x4"));
+    }
 }
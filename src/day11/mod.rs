@@ -1,30 +1,45 @@
-use std::{
-    collections::{HashMap, HashSet},
-    time::Instant,
+use std::collections::HashMap;
+
+use crate::{
+    parse::{list, ParseError},
+    solution::Solution,
 };
 
-const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
+pub const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
 
-pub(crate) fn run() {
-    println!("===== DAY ELEVEN =====");
-    // let now = Instant::now();
-    let part_one_result = part_one(PUZZLE_INPUT, 25);
-    println!("Part 1: {:?}", part_one_result);
-    let part_two_result = part_one(PUZZLE_INPUT, 75);
-    println!("Part 2: {:?}", part_two_result);
-}
+pub struct DayEleven;
 
-#[time_it::time_it]
-fn part_one(input: &str, n_blinks: usize) -> u64 {
-    let mut stones: HashMap<u64, u64> = HashMap::new();
-    for stone in input.split_whitespace() {
-        let num: u64 = stone.parse().expect("Input contains a non-integer value");
-        *stones.entry(num).or_insert(0) += 1;
+impl Solution for DayEleven {
+    const DAY: u8 = 11;
+    const PUZZLE_INPUT: &'static str = PUZZLE_INPUT;
+
+    type Input = HashMap<u64, u64>;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        let mut stones: HashMap<u64, u64> = HashMap::new();
+        for stone in list(input, input, " ")? {
+            *stones.entry(stone).or_insert(0) += 1;
+        }
+
+        Ok(stones)
     }
 
-    blink(stones, n_blinks, 0).values().sum()
+    fn part_one(stones: &Self::Input) -> u64 {
+        count_stones(stones, 25)
+    }
+
+    fn part_two(stones: &Self::Input) -> u64 {
+        count_stones(stones, 75)
+    }
 }
 
+#[time_it::time_it]
+fn count_stones(stones: &HashMap<u64, u64>, n_blinks: usize) -> u64 {
+    blink(stones.clone(), n_blinks, 0).values().sum()
+}
+
+#[time_it::time_it]
 fn blink(stones: HashMap<u64, u64>, n_blinks: usize, curr_blinks: usize) -> HashMap<u64, u64> {
     if curr_blinks == n_blinks {
         return stones;
@@ -52,31 +67,20 @@ fn blink(stones: HashMap<u64, u64>, n_blinks: usize, curr_blinks: usize) -> Hash
     blink(new_stones, n_blinks, curr_blinks + 1)
 }
 
-fn count_digits(n: &u64) -> u32 {
-    let mut n_digits: u32 = 0;
-    let mut temp = *n;
-
-    while temp > 0 {
-        n_digits += 1;
-        temp /= 10;
-    }
-
-    n_digits
-}
-
 #[cfg(test)]
 mod tests {
-    use super::part_one;
+    use super::{count_stones, DayEleven};
+    use crate::solution::Solution;
     const EXAMPLE_INPUT: &str = include_str!("./example_input.txt");
 
     mod part_one {
-        use super::EXAMPLE_INPUT;
+        use super::{count_stones, DayEleven, Solution, EXAMPLE_INPUT};
 
         #[test]
         fn part_one_example_returns_the_correct_answer() {
             let expected = 55312;
 
-            let actual = super::part_one(EXAMPLE_INPUT, 25);
+            let actual = DayEleven::solve_part_one(EXAMPLE_INPUT);
 
             assert_eq!(actual, expected)
         }
@@ -85,7 +89,7 @@ mod tests {
         fn part_one_simple() {
             let expected = 22;
 
-            let actual = super::part_one(EXAMPLE_INPUT, 6);
+            let actual = count_stones(&DayEleven::parse(EXAMPLE_INPUT).unwrap(), 6);
 
             assert_eq!(actual, expected);
         }
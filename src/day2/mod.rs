@@ -1,67 +1,203 @@
-const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
+use std::ops::RangeInclusive;
 
-pub(crate) fn run() {
-    println!("===== DAY TWO =====");
-    println!("Part 1: {:?}", part_one(PUZZLE_INPUT));
-    println!("Part 2: {:?}", part_two(PUZZLE_INPUT));
+use crate::{
+    parse::{list, ParseError},
+    solution::Solution,
+};
+
+pub const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
+
+pub struct DayTwo;
+
+impl Solution for DayTwo {
+    const DAY: u8 = 2;
+    const PUZZLE_INPUT: &'static str = PUZZLE_INPUT;
+
+    type Input = Vec<Vec<i32>>;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.lines().map(|line| list(input, line, " ")).collect()
+    }
+
+    fn part_one(reports: &Self::Input) -> usize {
+        let rule = SafetyRule::new(1..=3, Monotonicity::Either);
+
+        reports
+            .iter()
+            .filter(|report| rule.check(report).is_safe())
+            .count()
+    }
+
+    fn part_two(reports: &Self::Input) -> usize {
+        let rule = SafetyRule::new(1..=3, Monotonicity::Either).with_dampening(1);
+
+        reports
+            .iter()
+            .filter(|report| rule.check(report).is_safe())
+            .count()
+    }
 }
 
-fn part_one(input: &str) -> usize {
-    input
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|n| n.parse::<i32>().expect("failed to parse value in report"))
-                .collect::<Vec<i32>>()
-        })
-        .filter(report_is_safe)
-        .count()
+/// Which way the levels of a report have to go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Monotonicity {
+    Increasing,
+    Decreasing,
+    /// Either all increasing or all decreasing.
+    Either,
 }
 
-fn part_two(input: &str) -> usize {
-    input
-        .lines()
-        .map(|line| {
-            line.split_whitespace()
-                .map(|n| n.parse::<i32>().expect("failed to parse value in report"))
-                .collect::<Vec<i32>>()
-        })
-        .filter(report_is_safe_with_dampening)
-        .count()
+impl Monotonicity {
+    /// Signs a step is multiplied by before checking it against the allowed
+    /// steps, one per direction to try.
+    fn signs(self) -> &'static [i32] {
+        match self {
+            Monotonicity::Increasing => &[1],
+            Monotonicity::Decreasing => &[-1],
+            Monotonicity::Either => &[1, -1],
+        }
+    }
 }
 
-fn report_is_safe(report: &Vec<i32>) -> bool {
-    let is_increasing = report.windows(2).all(|w| {
-        let diff = w[1] - w[0];
-        diff >= 1 && diff <= 3
-    });
-    let is_decreasing = report.windows(2).all(|w| {
-        let diff = w[0] - w[1];
-        diff >= 1 && diff <= 3
-    });
-
-    is_decreasing || is_increasing
+/// When a report counts as safe: every step between neighbouring levels is in
+/// `steps` in the direction required by `monotonicity`, after removing at most
+/// `max_removals` levels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SafetyRule {
+    steps: RangeInclusive<i32>,
+    monotonicity: Monotonicity,
+    max_removals: usize,
 }
 
-fn report_is_safe_with_dampening(report: &Vec<i32>) -> bool {
-    if report_is_safe(&report) {
-        return true;
+/// The outcome of [`SafetyRule::check`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    /// Safe once the levels at these indices are removed, none if it was safe
+    /// as it is.
+    Safe { removed: Vec<usize> },
+    /// Unsafe even with the allowed removals. The step from level
+    /// `first_violation` to the next is the first one breaking the rule, in
+    /// the direction that got furthest.
+    Unsafe { first_violation: usize },
+}
+
+impl Verdict {
+    pub fn is_safe(&self) -> bool {
+        matches!(self, Verdict::Safe { .. })
+    }
+}
+
+impl SafetyRule {
+    /// A rule with the size of a step between levels in `steps` and no levels
+    /// allowed to be removed.
+    pub fn new(steps: RangeInclusive<i32>, monotonicity: Monotonicity) -> Self {
+        Self {
+            steps,
+            monotonicity,
+            max_removals: 0,
+        }
+    }
+
+    /// Allows removing up to `max_removals` levels to make a report safe.
+    pub fn with_dampening(self, max_removals: usize) -> Self {
+        Self {
+            max_removals,
+            ..self
+        }
     }
 
-    for i in 0..report.len() {
-        let mut dampened_levels = report.clone();
-        dampened_levels.remove(i);
+    /// Checks `report`, preferring the fewest removals and then increasing
+    /// levels. Takes `O(n × k)` for `k` allowed removals.
+    pub fn check(&self, report: &[i32]) -> Verdict {
+        let signs = self.monotonicity.signs();
+
+        let fewest_removals = signs
+            .iter()
+            .filter_map(|sign| self.removals(report, *sign))
+            .min_by_key(Vec::len);
 
-        if report_is_safe(&dampened_levels) {
-            return true;
+        match fewest_removals {
+            Some(removed) => Verdict::Safe { removed },
+            None => Verdict::Unsafe {
+                first_violation: signs
+                    .iter()
+                    .filter_map(|sign| {
+                        report
+                            .windows(2)
+                            .position(|w| !self.allows(w[0], w[1], *sign))
+                    })
+                    .max()
+                    .expect("an unsafe report breaks the rule somewhere"),
+            },
         }
     }
 
-    false
+    /// A step too large for an `i32` is never allowed.
+    fn allows(&self, from: i32, to: i32, sign: i32) -> bool {
+        to.checked_sub(from)
+            .and_then(|step| step.checked_mul(sign))
+            .is_some_and(|step| self.steps.contains(&step))
+    }
+
+    /// The fewest levels to remove for `report` to be safe in the direction of
+    /// `sign`, if there are no more than `max_removals`.
+    fn removals(&self, report: &[i32], sign: i32) -> Option<Vec<usize>> {
+        let n = report.len();
+        let k = self.max_removals;
+
+        if n == 0 {
+            return Some(Vec::new());
+        }
+
+        // For each level, the fewest removals before it for the levels kept so
+        // far to be safe, when it is kept, and the previous level kept. Only
+        // the last `k + 1` levels can come before it without removing more
+        // than `k` levels.
+        let mut best: Vec<Option<(usize, Option<usize>)>> = Vec::with_capacity(n);
+
+        for i in 0..n {
+            let mut current = (i <= k).then_some((i, None));
+
+            for j in (i.saturating_sub(k + 1)..i).rev() {
+                let Some((removed, _)) = best[j] else {
+                    continue;
+                };
+                let removed = removed + i - j - 1;
+
+                if removed <= k
+                    && current.is_none_or(|(fewest, _)| removed < fewest)
+                    && self.allows(report[j], report[i], sign)
+                {
+                    current = Some((removed, Some(j)));
+                }
+            }
+
+            best.push(current);
+        }
+
+        // The levels after the last kept one are removed too.
+        let (last, _) = (n.saturating_sub(k + 1)..n)
+            .filter_map(|i| best[i].map(|(removed, _)| (i, removed + n - 1 - i)))
+            .filter(|(_, removed)| *removed <= k)
+            .min_by_key(|(_, removed)| *removed)?;
+
+        let mut kept = vec![false; n];
+        let mut level = Some(last);
+
+        while let Some(i) = level {
+            kept[i] = true;
+            level = best[i].and_then(|(_, previous)| previous);
+        }
+
+        Some((0..n).filter(|i| !kept[*i]).collect())
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::{DayTwo, Monotonicity, SafetyRule, Verdict};
+    use crate::solution::Solution;
 
     const EXAMPLE_INPUT: &str = include_str!("./example_input.txt");
 
@@ -69,7 +205,7 @@ mod tests {
     fn part_one_example_returns_the_correct_answer() {
         let expected = 2;
 
-        let actual = super::part_one(EXAMPLE_INPUT);
+        let actual = DayTwo::solve_part_one(EXAMPLE_INPUT);
 
         assert_eq!(actual, expected)
     }
@@ -78,8 +214,70 @@ mod tests {
     fn part_two_example_returns_the_correct_answer() {
         let expected = 4;
 
-        let actual = super::part_two(EXAMPLE_INPUT);
+        let actual = DayTwo::solve_part_two(EXAMPLE_INPUT);
 
         assert_eq!(actual, expected)
     }
+
+    #[test]
+    fn explains_the_verdict() {
+        let rule = SafetyRule::new(1..=3, Monotonicity::Either).with_dampening(1);
+
+        let expected = vec![
+            Verdict::Safe { removed: vec![] },
+            Verdict::Unsafe { first_violation: 1 },
+            Verdict::Unsafe { first_violation: 2 },
+            Verdict::Safe { removed: vec![1] },
+            Verdict::Safe { removed: vec![2] },
+            Verdict::Safe { removed: vec![] },
+        ];
+        let actual = DayTwo::parse(EXAMPLE_INPUT)
+            .unwrap()
+            .iter()
+            .map(|report| rule.check(report))
+            .collect::<Vec<Verdict>>();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn removes_several_levels() {
+        let report = [1, 2, 7, 8, 3, 4];
+        let rule = SafetyRule::new(1..=3, Monotonicity::Increasing);
+
+        assert!(!rule.clone().with_dampening(1).check(&report).is_safe());
+        assert_eq!(
+            rule.with_dampening(2).check(&report),
+            Verdict::Safe {
+                removed: vec![2, 3]
+            }
+        );
+    }
+
+    #[test]
+    fn monotonicity_and_steps_are_configurable() {
+        let report = [10, 5, 0];
+
+        assert_eq!(
+            SafetyRule::new(1..=3, Monotonicity::Decreasing).check(&report),
+            Verdict::Unsafe { first_violation: 0 }
+        );
+        assert!(SafetyRule::new(5..=5, Monotonicity::Decreasing)
+            .check(&report)
+            .is_safe());
+        assert!(!SafetyRule::new(5..=5, Monotonicity::Increasing)
+            .check(&report)
+            .is_safe());
+    }
+
+    #[test]
+    fn steps_that_overflow_are_unsafe() {
+        let rule = SafetyRule::new(1..=i32::MAX, Monotonicity::Either);
+
+        assert_eq!(
+            rule.check(&[i32::MIN, i32::MAX]),
+            Verdict::Unsafe { first_violation: 0 }
+        );
+        assert!(!rule.check(&[i32::MAX, i32::MIN]).is_safe());
+    }
 }
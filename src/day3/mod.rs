@@ -1,83 +1,247 @@
-use regex::{Captures, Match, Regex};
+use std::ops::Range;
 
-const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
+use crate::{parse::ParseError, solution::Solution};
 
-pub(crate) fn run() {
-    println!("===== DAY THREE =====");
-    println!("Part 1: {:?}", part_one(PUZZLE_INPUT));
-    println!("Part 2: {:?}", part_two(PUZZLE_INPUT));
+pub const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
+
+pub struct DayThree;
+
+impl Solution for DayThree {
+    const DAY: u8 = 3;
+    const PUZZLE_INPUT: &'static str = PUZZLE_INPUT;
+
+    type Input = Vec<Token>;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(Lexer::new(input, &Operation::ALL).collect())
+    }
+
+    fn part_one(tokens: &Self::Input) -> u32 {
+        execute(tokens, &[Operation::MUL])
+    }
+
+    fn part_two(tokens: &Self::Input) -> u32 {
+        execute(tokens, &Operation::ALL)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Mul(u32, u32),
+    Do,
+    Dont,
+}
+
+/// The kind of an [`Instruction`], without its operands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Opcode {
+    Mul,
+    Do,
+    Dont,
+}
+
+impl Instruction {
+    pub fn opcode(&self) -> Opcode {
+        match self {
+            Instruction::Mul(..) => Opcode::Mul,
+            Instruction::Do => Opcode::Do,
+            Instruction::Dont => Opcode::Dont,
+        }
+    }
+}
+
+/// An instruction found in the memory, with the bytes it was read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub instruction: Instruction,
+    pub span: Range<usize>,
+}
+
+/// How one kind of instruction is read from memory and what it does when run.
+///
+/// Adding an instruction takes a variant in [`Instruction`] and [`Opcode`], an
+/// arm in [`Instruction::opcode`] and an `Operation` for it. Changing what an
+/// instruction does only takes a different `Operation`.
+#[derive(Debug, Clone, Copy)]
+pub struct Operation {
+    pub opcode: Opcode,
+    /// Reads the instruction from the start of the scanner, if it is there.
+    pub recognise: fn(&mut Scanner) -> Option<Instruction>,
+    pub execute: fn(&mut Machine, Instruction),
 }
 
-fn part_one(input: &str) -> u32 {
-    let mul_captures_re = Regex::new(r"mul\((?<lhs>\d{1,3})\,(?<rhs>\d{1,3})\)")
-        .expect("failed to create regex for part 1");
+impl Operation {
+    /// `mul(a,b)` with up to three digits each, adding `a * b` to the total
+    /// while enabled.
+    pub const MUL: Operation = Operation {
+        opcode: Opcode::Mul,
+        recognise: |s| {
+            s.literal("mul(")?;
+            let lhs = s.number(3)?;
+            s.literal(",")?;
+            let rhs = s.number(3)?;
+            s.literal(")")?;
+
+            Some(Instruction::Mul(lhs, rhs))
+        },
+        execute: |machine, instruction| {
+            if let Instruction::Mul(lhs, rhs) = instruction {
+                if machine.enabled {
+                    machine.total += lhs * rhs;
+                }
+            }
+        },
+    };
 
-    mul_captures_re
-        .captures_iter(input)
-        .map(|cap| {
-            let lhs: u32 = cap["lhs"].parse().expect("failed to parse lhs");
-            let rhs: u32 = cap["rhs"].parse().expect("f?ailed to parse rhs");
+    /// `do()`, enabling the multiplications that follow.
+    pub const DO: Operation = Operation {
+        opcode: Opcode::Do,
+        recognise: |s| s.literal("do()").map(|_| Instruction::Do),
+        execute: |machine, _| machine.enabled = true,
+    };
 
-            lhs * rhs
-        })
-        .sum()
+    /// `don't()`, disabling the multiplications that follow.
+    pub const DONT: Operation = Operation {
+        opcode: Opcode::Dont,
+        recognise: |s| s.literal("don't()").map(|_| Instruction::Dont),
+        execute: |machine, _| machine.enabled = false,
+    };
+
+    pub const ALL: [Operation; 3] = [Operation::MUL, Operation::DO, Operation::DONT];
 }
 
-fn part_two(input: &str) -> u32 {
-    let mut include = true;
-    let mul_re =
-        Regex::new(r"mul\((?<lhs>\d{1,3})\,(?<rhs>\d{1,3})\)").expect("failed to create mul regex");
-    let inst_re =
-        Regex::new(r"(?<inst>do(n\'t)?\(\))").expect("failed to create instruction regex");
+/// The rest of the memory, as seen by [`Operation::recognise`].
+pub struct Scanner<'a> {
+    rest: &'a str,
+    consumed: usize,
+}
 
-    let mul_captures = mul_re.captures_iter(input);
-    let inst_captures = inst_re.captures_iter(input);
+impl Scanner<'_> {
+    fn advance(&mut self, len: usize) {
+        self.rest = &self.rest[len..];
+        self.consumed += len;
+    }
 
-    let mut captures = mul_captures.chain(inst_captures).collect::<Vec<Captures>>();
+    pub fn literal(&mut self, literal: &str) -> Option<()> {
+        self.rest
+            .starts_with(literal)
+            .then(|| self.advance(literal.len()))
+    }
 
-    captures.sort_by(|cap_a, cap_b| {
-        let cap_a_start = cap_a.get(0).unwrap().start();
-        let cap_b_start = cap_b.get(0).unwrap().start();
+    /// Between one and `max_digits` digits.
+    pub fn number(&mut self, max_digits: usize) -> Option<u32> {
+        let len = self
+            .rest
+            .bytes()
+            .take(max_digits + 1)
+            .take_while(u8::is_ascii_digit)
+            .count();
 
-        cap_a_start.cmp(&cap_b_start)
-    });
+        if len == 0 || len > max_digits {
+            return None;
+        }
 
-    captures
-        .into_iter()
-        .map(|cap| {
-            let instruction = cap.name("inst");
+        let number = self.rest[..len].parse().ok()?;
+        self.advance(len);
+
+        Some(number)
+    }
+}
+
+/// Finds every instruction of `operations` in corrupted memory in a single
+/// pass, skipping anything that isn't one. Operations are tried in order at
+/// each position.
+pub struct Lexer<'a> {
+    memory: &'a str,
+    operations: &'a [Operation],
+    position: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(memory: &'a str, operations: &'a [Operation]) -> Self {
+        Self {
+            memory,
+            operations,
+            position: 0,
+        }
+    }
+}
 
-            include = match instruction {
-                Some(m) if m.as_str() == "don't()" => false,
-                Some(m) if m.as_str() == "do()" => true,
-                _ => include,
-            };
+impl Iterator for Lexer<'_> {
+    type Item = Token;
 
-            if !include {
-                return 0;
+    fn next(&mut self) -> Option<Token> {
+        while let Some(c) = self.memory[self.position..].chars().next() {
+            let start = self.position;
+
+            for operation in self.operations {
+                let mut scanner = Scanner {
+                    rest: &self.memory[start..],
+                    consumed: 0,
+                };
+
+                if let Some(instruction) = (operation.recognise)(&mut scanner) {
+                    self.position += scanner.consumed;
+
+                    return Some(Token {
+                        instruction,
+                        span: start..self.position,
+                    });
+                }
             }
 
-            let lhs: u32 = cap
-                .name("lhs")
-                .map_or(0, |n| n.as_str().parse().expect("failed to parse lhs"));
-            let rhs: u32 = cap
-                .name("rhs")
-                .map_or(0, |n| n.as_str().parse().expect("failed to parse rhs"));
+            self.position += c.len_utf8();
+        }
 
-            lhs * rhs
-        })
-        .sum()
+        None
+    }
+}
+
+/// State of the machine running the instructions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Machine {
+    pub enabled: bool,
+    pub total: u32,
+}
+
+impl Default for Machine {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            total: 0,
+        }
+    }
+}
+
+/// Runs each instruction with the operation for its opcode in `operations`,
+/// ignoring instructions without one, and returns the sum of the
+/// multiplications.
+pub fn execute<'a>(tokens: impl IntoIterator<Item = &'a Token>, operations: &[Operation]) -> u32 {
+    let mut machine = Machine::default();
+
+    for token in tokens {
+        let opcode = token.instruction.opcode();
+
+        if let Some(operation) = operations.iter().find(|op| op.opcode == opcode) {
+            (operation.execute)(&mut machine, token.instruction);
+        }
+    }
+
+    machine.total
 }
 
 #[cfg(test)]
 mod tests {
+    use super::{execute, DayThree, Instruction, Lexer, Operation, Token};
+    use crate::solution::Solution;
 
     #[test]
     fn part_one_example_returns_the_correct_answer() {
         let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
         let expected = 161;
 
-        let actual = super::part_one(input);
+        let actual = DayThree::solve_part_one(input);
 
         assert_eq!(actual, expected)
     }
@@ -87,7 +251,7 @@ mod tests {
         let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
         let expected = 48;
 
-        let actual = super::part_two(input);
+        let actual = DayThree::solve_part_two(input);
 
         assert_eq!(actual, expected)
     }
@@ -98,8 +262,67 @@ mod tests {
 _mul(500,15)don't()+mul(32,64](mul(11,8)undo()?mul(8,5))";
         let expected = 80;
 
-        let actual = super::part_two(input);
+        let actual = DayThree::solve_part_two(input);
 
         assert_eq!(actual, expected)
     }
+
+    #[test]
+    fn lexer_yields_instructions_with_spans() {
+        let input = "mul(mul(2,4)do()!don't()mul(1234,5)mul(7,8)";
+
+        let expected = vec![
+            Token {
+                instruction: Instruction::Mul(2, 4),
+                span: 4..12,
+            },
+            Token {
+                instruction: Instruction::Do,
+                span: 12..16,
+            },
+            Token {
+                instruction: Instruction::Dont,
+                span: 17..24,
+            },
+            Token {
+                instruction: Instruction::Mul(7, 8),
+                span: 35..43,
+            },
+        ];
+        let actual = Lexer::new(input, &Operation::ALL).collect::<Vec<Token>>();
+
+        assert_eq!(actual, expected);
+        assert_eq!(&input[actual[2].span.clone()], "don't()");
+    }
+
+    #[test]
+    fn instruction_set_decides_what_runs() {
+        let tokens = Lexer::new("mul(2,3)don't()mul(4,5)do()mul(1,1)", &Operation::ALL)
+            .collect::<Vec<Token>>();
+
+        assert_eq!(execute(&tokens, &[Operation::MUL]), 27);
+        assert_eq!(execute(&tokens, &[Operation::MUL, Operation::DONT]), 6);
+        assert_eq!(execute(&tokens, &Operation::ALL), 7);
+    }
+
+    #[test]
+    fn semantics_are_configurable() {
+        let tokens = Lexer::new("mul(2,3)don't()mul(4,5)", &Operation::ALL).collect::<Vec<Token>>();
+
+        // A `don't()` that doubles everything instead of disabling.
+        let double = Operation {
+            execute: |machine, _| machine.total *= 2,
+            ..Operation::DONT
+        };
+
+        assert_eq!(execute(&tokens, &[Operation::MUL, double]), 32);
+    }
+
+    #[test]
+    fn lexer_only_reads_the_given_operations() {
+        let tokens = Lexer::new("do()mul(1,2)don't()", &[Operation::MUL]).collect::<Vec<Token>>();
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].instruction, Instruction::Mul(1, 2));
+    }
 }
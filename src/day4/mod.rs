@@ -1,92 +1,92 @@
-use crate::models::matrix::Matrix;
-
-const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
-
-type Direction = (i32, i32);
-
-const DIRECTIONS: [Direction; 8] = [
-    // (x, y)
-    // nw
-    (-1, -1),
-    // n
-    (0, -1),
-    // ne
-    (1, -1),
-    // e
-    (1, 0),
-    // se
-    (1, 1),
-    // s
-    (0, 1),
-    // sw
-    (-1, 1),
-    // w
-    (-1, 0),
-];
+use crate::{
+    models::{direction::Direction, grid::Grid, point::Position, view::GridView},
+    parse::ParseError,
+    solution::Solution,
+};
 
-pub(crate) fn run() {
-    println!("===== DAY FOUR =====");
-    println!("Part 1: {:?}", part_one(PUZZLE_INPUT));
-    println!("Part 2: {:?}", part_two(PUZZLE_INPUT));
-}
+pub const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
+
+pub struct DayFour;
 
-fn part_one(input: &str) -> u32 {
-    let matrix = Matrix::from(input);
+impl Solution for DayFour {
+    const DAY: u8 = 4;
+    const PUZZLE_INPUT: &'static str = PUZZLE_INPUT;
+
+    type Input = Grid<char>;
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
 
-    let mut total: u32 = 0;
+    fn part_one(grid: &Self::Input) -> u32 {
+        let mut total: u32 = 0;
 
-    for (x, y) in matrix.pairs() {
-        for direction in DIRECTIONS {
-            let string = check_direction(&matrix, x, y, &direction, 3);
-            if string == "XMAS" {
-                total += 1
+        for position in grid.positions() {
+            for direction in Direction::ALL {
+                let string = check_direction(grid, position, direction, 3);
+                if string == "XMAS" {
+                    total += 1
+                }
             }
         }
+
+        total
     }
 
-    total
+    fn part_two(grid: &Self::Input) -> u32 {
+        grid.view()
+            .windows(3)
+            .filter(|(_, window)| is_x_mas(*window))
+            .count() as u32
+    }
 }
 
-fn check_direction(
-    matrix: &Matrix<String>,
-    x: usize,
-    y: usize,
-    direction: &Direction,
-    count: i32,
-) -> String {
+/// The cells of an X-MAS with both `MAS` going down, as `(x, y, letter)`:
+///
+/// ```text
+/// M.S
+/// .A.
+/// M.S
+/// ```
+const X_MAS: [(usize, usize, char); 5] = [
+    (0, 0, 'M'),
+    (2, 0, 'S'),
+    (1, 1, 'A'),
+    (0, 2, 'M'),
+    (2, 2, 'S'),
+];
+
+/// Whether a 3×3 window holds an X-MAS. Every way to write one is [`X_MAS`]
+/// turned a number of quarter turns.
+fn is_x_mas(window: GridView<char>) -> bool {
+    [
+        window,
+        window.rotate_90(),
+        window.rotate_180(),
+        window.rotate_270(),
+    ]
+    .iter()
+    .any(|rotated| {
+        X_MAS
+            .iter()
+            .all(|(x, y, letter)| rotated.get(Position::new(*x, *y)) == Some(letter))
+    })
+}
+
+fn check_direction(grid: &Grid<char>, from: Position, direction: Direction, count: i32) -> String {
     (0..=count)
-        .map(|step| {
-            let x = (x as i32 + step * direction.0) as usize;
-            let y = (y as i32 + step * direction.1) as usize;
-            matrix.get(x, y)
+        .map_while(|step| {
+            from.checked_offset(direction.offset() * step)
+                .and_then(|position| grid.get(position))
         })
         .collect()
 }
 
-fn part_two(input: &str) -> u32 {
-    let matrix = Matrix::from(input);
-
-    let mut total: u32 = 0;
-
-    for (x, y) in matrix.pairs() {
-        let forward_slash = check_direction(&matrix, x, y, &(1, 1), 2);
-        let back_slash = check_direction(&matrix, x + 2, y, &(-1, 1), 2);
-
-        let mas = String::from("MAS");
-        let sam = String::from("SAM");
-
-        if (forward_slash == mas || forward_slash == sam)
-            && (back_slash == mas || back_slash == sam)
-        {
-            total += 1
-        }
-    }
-
-    total
-}
-
 #[cfg(test)]
 mod tests {
+    use super::DayFour;
+    use crate::solution::Solution;
 
     const EXAMPLE_INPUT: &str = include_str!("./example_input.txt");
 
@@ -94,7 +94,7 @@ mod tests {
     fn part_one_example_returns_the_correct_answer() {
         let expected = 18;
 
-        let actual = super::part_one(EXAMPLE_INPUT);
+        let actual = DayFour::solve_part_one(EXAMPLE_INPUT);
 
         assert_eq!(actual, expected)
     }
@@ -103,7 +103,7 @@ mod tests {
     fn part_two_example_returns_the_correct_answer() {
         let expected = 9;
 
-        let actual = super::part_two(EXAMPLE_INPUT);
+        let actual = DayFour::solve_part_two(EXAMPLE_INPUT);
 
         assert_eq!(actual, expected)
     }
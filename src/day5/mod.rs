@@ -3,24 +3,55 @@ use std::{
     collections::{HashMap, HashSet},
 };
 
-const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
+use crate::{
+    parse::{key_values, list, number, sections, ParseError},
+    solution::Solution,
+};
+
+pub const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
+
+pub struct DayFive;
+
+impl Solution for DayFive {
+    const DAY: u8 = 5;
+    const PUZZLE_INPUT: &'static str = PUZZLE_INPUT;
+
+    type Input = (OrderingRules, PrintOrders);
+    type Answer = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
 
-pub(crate) fn run() {
-    println!("===== DAY FIVE =====");
-    println!("Part 1: {:?}", part_one(PUZZLE_INPUT));
-    println!("Part 2: {:?}", part_two(PUZZLE_INPUT));
+    fn part_one((ordering_rules, print_orders): &Self::Input) -> u32 {
+        print_orders
+            .iter()
+            .filter(|order| ordering_rules.is_valid_order(order))
+            .map(|order| take_middle(order))
+            .sum()
+    }
+
+    fn part_two((ordering_rules, print_orders): &Self::Input) -> u32 {
+        print_orders
+            .iter()
+            .filter(|order| !ordering_rules.is_valid_order(order))
+            .map(|order| ordering_rules.repair_order(order))
+            .map(|order| take_middle(&order))
+            .sum()
+    }
 }
 
-type PrintOrders = Vec<Vec<u32>>;
+pub type PrintOrders = Vec<Vec<u32>>;
 
-struct OrderingRules(HashMap<u32, HashSet<u32>>);
+#[derive(Debug)]
+pub struct OrderingRules(HashMap<u32, HashSet<u32>>);
 
 impl OrderingRules {
     fn get(&self, page_number: u32) -> Option<&HashSet<u32>> {
         self.0.get(&page_number)
     }
 
-    fn is_valid_order(&self, order: &Vec<u32>) -> bool {
+    fn is_valid_order(&self, order: &[u32]) -> bool {
         for i in 0..order.len() {
             let page = order.get(i).unwrap();
 
@@ -38,8 +69,8 @@ impl OrderingRules {
         true
     }
 
-    fn repair_order(&self, order: &Vec<u32>) -> Vec<u32> {
-        let mut repaired_order = order.clone();
+    fn repair_order(&self, order: &[u32]) -> Vec<u32> {
+        let mut repaired_order = order.to_vec();
 
         repaired_order.sort_by(|a, b| {
             if let Some(rules) = self.get(*a) {
@@ -54,85 +85,76 @@ impl OrderingRules {
     }
 }
 
-impl From<&&str> for OrderingRules {
-    fn from(value: &&str) -> Self {
+impl OrderingRules {
+    /// Parses the `X|Y` rules in `section`, a slice of `input`.
+    fn parse(input: &str, section: &str) -> Result<Self, ParseError> {
         let mut map: HashMap<u32, HashSet<u32>> = HashMap::new();
 
-        for line in value.lines() {
-            let temp = line
-                .split("|")
-                .take(2)
-                .map(|n| n.parse().expect("Failed to parse n in ordering rule"))
-                .collect::<Vec<u32>>();
+        for line in section.lines() {
+            let (key, value) = key_values(input, line, "|")?;
 
-            let key = *temp.get(0).expect("failed to get key");
-            let value = *temp.get(1).expect("failed to get value");
-
-            map.entry(key).or_insert_with(HashSet::new).insert(value);
+            map.entry(number(input, key)?)
+                .or_default()
+                .insert(number(input, value)?);
         }
 
-        Self(map)
+        Ok(Self(map))
     }
 }
 
-fn part_one(input: &str) -> u32 {
-    let (ordering_rules, print_orders) = parse_input(input);
+fn parse_input(input: &str) -> Result<(OrderingRules, PrintOrders), ParseError> {
+    let mut sections = sections(input);
+
+    let (Some(rules), Some(orders)) = (sections.next(), sections.next()) else {
+        let end = input.trim_end();
+
+        return Err(ParseError::at(
+            input,
+            &end[end.len()..],
+            "expected a blank line between the rules and the print orders",
+        ));
+    };
+
+    if let Some(extra) = sections.next() {
+        return Err(ParseError::at(
+            input,
+            extra,
+            "expected nothing after the print orders",
+        ));
+    }
 
-    print_orders
-        .iter()
-        .filter(|order| ordering_rules.is_valid_order(order))
-        .map(take_middle)
-        .sum()
-}
+    let ordering_rules = OrderingRules::parse(input, rules)?;
 
-fn part_two(input: &str) -> u32 {
-    let (ordering_rules, print_orders) = parse_input(input);
+    let print_orders = orders
+        .lines()
+        .map(|line| {
+            let order = list(input, line, ",")?;
 
-    print_orders
-        .iter()
-        .filter(|order| !ordering_rules.is_valid_order(order))
-        .map(|order| ordering_rules.repair_order(order))
-        .map(|order| take_middle(&order))
-        .sum()
-}
+            // Only orders with a middle page can be scored.
+            if order.len() % 2 == 0 {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    format!("expected an odd number of pages, got {}", order.len()),
+                ));
+            }
 
-fn parse_input(input: &str) -> (OrderingRules, PrintOrders) {
-    let split = input.split("\n\n").take(2).collect::<Vec<&str>>();
-
-    let ordering_rules = split
-        .get(0)
-        .map(OrderingRules::from)
-        .expect("Failed to get ordering rules");
-
-    let print_orders = split
-        .get(1)
-        .map(|orders| {
-            orders
-                .lines()
-                .map(|line| {
-                    line.split(",")
-                        .map(|page_number| {
-                            page_number.parse().expect("failed to parse page number")
-                        })
-                        .collect()
-                })
-                .collect::<Vec<Vec<u32>>>()
+            Ok(order)
         })
-        .expect("Failed to get print orders");
+        .collect::<Result<PrintOrders, ParseError>>()?;
 
-    (ordering_rules, print_orders)
+    Ok((ordering_rules, print_orders))
 }
 
-fn take_middle(order: &Vec<u32>) -> u32 {
-    let middle_index = order.len() / 2;
-
-    *order
-        .get(middle_index)
-        .expect("unable to find the middle value")
+/// Orders always have a middle page, [`parse_input`] rejects the others.
+fn take_middle(order: &[u32]) -> u32 {
+    order[order.len() / 2]
 }
 
 #[cfg(test)]
 mod tests {
+    use super::DayFive;
+    use crate::solution::Solution;
 
     const EXAMPLE_INPUT: &str = include_str!("./example_input.txt");
 
@@ -140,7 +162,7 @@ mod tests {
     fn part_one_example_returns_the_correct_answer() {
         let expected = 143;
 
-        let actual = super::part_one(EXAMPLE_INPUT);
+        let actual = DayFive::solve_part_one(EXAMPLE_INPUT);
 
         assert_eq!(actual, expected)
     }
@@ -149,8 +171,26 @@ mod tests {
     fn part_two_example_returns_the_correct_answer() {
         let expected = 123;
 
-        let actual = super::part_two(EXAMPLE_INPUT);
+        let actual = DayFive::solve_part_two(EXAMPLE_INPUT);
 
         assert_eq!(actual, expected)
     }
+
+    #[test]
+    fn rejects_extra_sections() {
+        let input = "47|53\n\n75,47,61\n\n1,2,3";
+
+        let err = DayFive::parse(input).unwrap_err();
+
+        assert_eq!((err.line, err.column), (5, 1));
+    }
+
+    #[test]
+    fn rejects_orders_without_a_middle_page() {
+        let input = "47|53\n\n75,47,61\n75,47";
+
+        let err = DayFive::parse(input).unwrap_err();
+
+        assert_eq!((err.line, err.text.as_str()), (4, "75,47"));
+    }
 }
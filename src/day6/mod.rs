@@ -1,84 +1,143 @@
-use std::{cmp::Ordering, collections::HashSet, hash::Hash};
+use std::{collections::HashSet, str::FromStr};
 
 use rayon::prelude::*;
 
-use crate::models::position::{Distance, Position};
+use crate::{
+    models::{
+        bounding_box::BoundingBox, direction::Direction, grid::Grid, point::Position, render::Style,
+    },
+    parse::ParseError,
+    solution::Solution,
+};
 
-use std::time::Instant;
+pub const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
 
-use crate::models::matrix::{BoundingBox, Matrix};
+pub struct DaySix;
 
-const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
+impl Solution for DaySix {
+    const DAY: u8 = 6;
+    const PUZZLE_INPUT: &'static str = PUZZLE_INPUT;
 
-pub(crate) fn run() {
-    println!("===== DAY SIX =====");
-    let now = Instant::now();
-    let part_one_result = part_one(PUZZLE_INPUT);
-    println!("({:.2?}) Part 1: {:?}", now.elapsed(), part_one_result);
-    let now = Instant::now();
-    let part_two_result = part_two(PUZZLE_INPUT);
-    println!("({:.2?}) Part 2: {:?}", now.elapsed(), part_two_result);
-}
+    type Input = Lab;
+    type Answer = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
+
+    fn part_one(lab: &Self::Input) -> usize {
+        walk_the_guard(&lab.guard, &lab.obstacles(), &lab.bounding_box)
+            .map_or(0, |visited| visited.len())
+    }
 
-fn part_one(input: &str) -> usize {
-    let matrix = Matrix::from(input);
-    let obstacles = get_obstacles(&matrix);
+    fn part_two(lab: &Self::Input) -> usize {
+        let obstacles = lab.obstacles();
+        let guard = lab.guard;
+        let bounding_box = &lab.bounding_box;
 
-    let guard = get_guard_position(&matrix);
+        let Some(potential_obstacles) = walk_the_guard(&guard, &obstacles, bounding_box) else {
+            return 0;
+        };
+
+        potential_obstacles
+            .par_iter()
+            .filter_map(|pos| {
+                if pos == &guard {
+                    return None;
+                }
+
+                let mut next_obstacles = obstacles.clone();
 
-    walk_the_guard(&guard, &obstacles, &matrix.get_bounding_box())
-        .map_or(0, |visited| visited.len())
+                next_obstacles.insert(*pos);
+
+                match walk_the_guard(&guard, &next_obstacles, bounding_box) {
+                    Some(_) => None,
+                    None => Some(1),
+                }
+            })
+            .sum()
+    }
 }
 
-fn part_two(input: &str) -> usize {
-    let matrix = Matrix::from(input);
-    let obstacles = get_obstacles(&matrix);
+/// The map of the lab, with the guard's starting position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lab {
+    grid: Grid<char>,
+    guard: Position,
+    bounding_box: BoundingBox<usize>,
+}
 
-    let guard = get_guard_position(&matrix);
+impl Lab {
+    fn obstacles(&self) -> HashSet<Position> {
+        self.grid.positions_of(&'#').collect()
+    }
+}
 
-    let potential_obstacles =
-        walk_the_guard(&guard, &obstacles, &matrix.get_bounding_box()).unwrap();
+impl FromStr for Lab {
+    type Err = ParseError;
 
-    potential_obstacles
-        .par_iter()
-        .filter_map(|pos| {
-            if pos == &guard {
-                return None;
-            }
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let grid = Grid::try_parse(input, |c| ".#^".contains(c).then_some(c))?;
+        let end = input.trim_end();
+        let end = &end[end.len()..];
+
+        let Some(bounding_box) = grid.bounding_box() else {
+            return Err(ParseError::at(input, end, "the map is empty"));
+        };
 
-            let mut next_obstacles = obstacles.clone();
+        let mut guards = input.match_indices('^');
+
+        if guards.next().is_none() {
+            return Err(ParseError::at(input, end, "expected a guard `^`"));
+        }
+
+        if let Some((i, guard)) = guards.next() {
+            return Err(ParseError::at(
+                input,
+                &input[i..i + guard.len()],
+                "expected a single guard",
+            ));
+        }
 
-            next_obstacles.insert(pos.clone());
+        let guard = grid.positions_of(&'^').next().unwrap();
 
-            match walk_the_guard(&guard, &next_obstacles, &matrix.get_bounding_box()) {
-                Some(_) => None,
-                None => Some(1),
-            }
+        Ok(Self {
+            grid,
+            guard,
+            bounding_box,
         })
-        .sum()
+    }
+}
+
+/// Draws the lab with every cell the guard walks through marked with an `X`.
+pub fn render_walk(lab: &Lab) -> String {
+    let visited =
+        walk_the_guard(&lab.guard, &lab.obstacles(), &lab.bounding_box).unwrap_or_default();
+
+    lab.grid
+        .render(|c| *c)
+        .overlay(visited, Style::glyph('X'))
+        .to_string()
 }
 
 fn walk_the_guard(
     initial_position: &Position,
     obstacles: &HashSet<Position>,
-    bounding_box: &BoundingBox,
+    bounding_box: &BoundingBox<usize>,
 ) -> Option<HashSet<Position>> {
     let mut visited: HashSet<Position> = HashSet::new();
     let mut visited_states: HashSet<(Position, Direction)> = HashSet::new();
 
-    visited.insert(initial_position.clone());
+    visited.insert(*initial_position);
 
     let mut walk_result = WalkResult {
         finished: false,
         next_direction: Direction::North,
-        next_position: initial_position.clone(),
+        next_position: *initial_position,
     };
 
     while !walk_result.finished {
-        let current_state = (
-            walk_result.next_position.clone(),
-            walk_result.next_direction.clone(),
-        );
+        let current_state = (walk_result.next_position, walk_result.next_direction);
 
         if visited_states.contains(&current_state) {
             return None;
@@ -99,30 +158,7 @@ fn walk_the_guard(
     Some(visited)
 }
 
-fn get_obstacles(matrix: &Matrix<String>) -> HashSet<Position> {
-    matrix
-        .iter_with_pos()
-        .map(|(value, position)| {
-            if value == "#" {
-                return Some(position);
-            } else {
-                return None;
-            }
-        })
-        .filter(|pos| pos.is_some())
-        .map(|pos| pos.unwrap())
-        .collect()
-}
-fn get_guard_position(matrix: &Matrix<String>) -> Position {
-    matrix
-        .find_position_by(is_guard)
-        .expect("Failed to find guard position")
-}
-
-fn is_guard(character: String) -> bool {
-    character == "^"
-}
-
+#[cfg(test)]
 fn find_next_obstacle(
     obstacles: &HashSet<Position>,
     from_point: &Position,
@@ -133,6 +169,7 @@ fn find_next_obstacle(
         Direction::South => pos.south_of(from_point),
         Direction::East => pos.east_of(from_point),
         Direction::West => pos.west_of(from_point),
+        _ => unreachable!("the guard only walks north, east, south or west"),
     });
 
     match direction {
@@ -140,6 +177,7 @@ fn find_next_obstacle(
         Direction::South => it.min_by(|a, b| a.compare_y(b)),
         Direction::East => it.min_by(|a, b| a.compare_x(b)),
         Direction::West => it.max_by(|a, b| a.compare_x(b)),
+        _ => unreachable!("the guard only walks north, east, south or west"),
     }
     .cloned()
 }
@@ -148,28 +186,23 @@ fn walk(
     current_position: &Position,
     obstacles: &HashSet<Position>,
     direction: &Direction,
-    bounding_box: &BoundingBox,
+    bounding_box: &BoundingBox<usize>,
 ) -> WalkResult {
-    let mut visited: HashSet<Position> = HashSet::new();
+    let next_position = current_position
+        .checked_offset(direction.offset())
+        .filter(|position| bounding_box.contains(*position));
 
-    let next_position = match direction {
-        Direction::North => current_position.add(0, -1),
-        Direction::East => current_position.add(1, 0),
-        Direction::South => current_position.add(0, 1),
-        Direction::West => current_position.add(-1, 0),
-    };
-
-    if !bounding_box.contains(&next_position) {
+    let Some(next_position) = next_position else {
         return WalkResult {
-            next_position: current_position.clone(),
+            next_position: *current_position,
             finished: true,
-            next_direction: direction.clone(),
+            next_direction: *direction,
         };
-    }
+    };
 
     if obstacles.contains(&next_position) {
         return WalkResult {
-            next_position: current_position.clone(),
+            next_position: *current_position,
             finished: false,
             next_direction: direction.turn_right(),
         };
@@ -178,36 +211,7 @@ fn walk(
     WalkResult {
         next_position,
         finished: false,
-        next_direction: direction.clone(),
-    }
-}
-
-fn walk_forward(position: &Position, direction: &Direction) -> Position {
-    match direction {
-        Direction::North => position.add(0, -1),
-        Direction::East => position.add(1, 0),
-        Direction::South => position.add(0, 1),
-        Direction::West => position.add(-1, 0),
-    }
-    .clone()
-}
-
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
-enum Direction {
-    North,
-    South,
-    East,
-    West,
-}
-
-impl Direction {
-    fn turn_right(&self) -> Direction {
-        match self {
-            Direction::North => Direction::East,
-            Direction::East => Direction::South,
-            Direction::South => Direction::West,
-            Direction::West => Direction::North,
-        }
+        next_direction: *direction,
     }
 }
 
@@ -225,15 +229,16 @@ mod tests {
     mod parts {
         const EXAMPLE_INPUT: &str = include_str!("./example_input.txt");
 
-        use crate::day6::PUZZLE_INPUT;
-
-        use super::super::{part_one, part_two};
+        use crate::{
+            day6::{DaySix, PUZZLE_INPUT},
+            solution::Solution,
+        };
 
         #[test]
         fn part_one_example_returns_the_correct_answer() {
             let expected = 41;
 
-            let actual = part_one(EXAMPLE_INPUT);
+            let actual = DaySix::solve_part_one(EXAMPLE_INPUT);
 
             assert_eq!(actual, expected)
         }
@@ -242,7 +247,7 @@ mod tests {
         fn part_one_actual_input() {
             let expected = 4776;
 
-            let actual = part_one(PUZZLE_INPUT);
+            let actual = DaySix::solve_part_one(PUZZLE_INPUT);
 
             assert_eq!(actual, expected);
         }
@@ -251,7 +256,7 @@ mod tests {
         fn part_two_example_returns_the_correct_answer() {
             let expected = 6;
 
-            let actual = part_two(EXAMPLE_INPUT);
+            let actual = DaySix::solve_part_two(EXAMPLE_INPUT);
 
             assert_eq!(actual, expected)
         }
@@ -260,12 +265,67 @@ mod tests {
         fn part_two_actual_input() {
             let expected = 1586;
 
-            let actual = part_two(PUZZLE_INPUT);
+            let actual = DaySix::solve_part_two(PUZZLE_INPUT);
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    mod rendering {
+        use crate::{
+            day6::{render_walk, DaySix},
+            solution::Solution,
+        };
+
+        const EXAMPLE_INPUT: &str = include_str!("./example_input.txt");
+
+        #[test]
+        fn visited_cells_are_marked() {
+            let expected = "\
+....#.....
+....XXXXX#
+....X...X.
+..#.X...X.
+..XXXXX#X.
+..X.X.X.X.
+.#XXXXXXX.
+.XXXXXXX#.
+#XXXXXXX..
+......#X..
+";
+
+            let actual = render_walk(&DaySix::parse(EXAMPLE_INPUT).unwrap());
 
             assert_eq!(actual, expected);
         }
     }
 
+    mod parsing {
+        use crate::{day6::DaySix, solution::Solution};
+
+        #[test]
+        fn guard_is_required() {
+            let err = DaySix::parse("..\n.#\n").unwrap_err();
+
+            assert_eq!((err.line, err.column), (2, 3));
+            assert_eq!(err.message, "expected a guard `^`");
+        }
+
+        #[test]
+        fn only_one_guard_is_allowed() {
+            let err = DaySix::parse(".^\n^#").unwrap_err();
+
+            assert_eq!((err.line, err.column, err.text.as_str()), (2, 1, "^"));
+        }
+
+        #[test]
+        fn map_is_required() {
+            let err = DaySix::parse("").unwrap_err();
+
+            assert_eq!(err.message, "the map is empty");
+        }
+    }
+
     mod infinite_loops {
         use std::collections::HashSet;
 
@@ -290,10 +350,7 @@ mod tests {
             let result = walk_the_guard(
                 &start_position,
                 &obstacles,
-                &BoundingBox {
-                    top_left: Position::new(0, 0),
-                    bottom_right: Position::new(4, 4),
-                },
+                &BoundingBox::new(Position::new(0, 0), Position::new(4, 4)),
             );
 
             assert_eq!(result, None);
@@ -1,66 +1,71 @@
 use rayon::prelude::*;
-use std::time::Instant;
-
-const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
-
-pub(crate) fn run() {
-    println!("===== DAY SEVEN =====");
-    let now = Instant::now();
-    let part_one_result = part_one(PUZZLE_INPUT);
-    println!("({:.2?}) Part 1: {:?}", now.elapsed(), part_one_result);
-    let now = Instant::now();
-    let part_two_result = part_two(PUZZLE_INPUT);
-    println!("({:.2?}) Part 2: {:?}", now.elapsed(), part_two_result);
-}
 
-fn part_one(input: &str) -> u64 {
-    input
-        .par_lines()
-        .filter_map(|line| {
-            let mut parsed = parse_line(line);
+use crate::{
+    parse::{key_values, list, number, ParseError},
+    solution::Solution,
+};
 
-            let values = parsed.split_off(1);
+pub const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
 
-            let result = parsed.get(0).unwrap();
+pub struct DaySeven;
 
-            if evaluate(result, &values, 2) {
-                return Some(*result);
-            } else {
-                return None;
-            }
-        })
-        .sum()
-}
+impl Solution for DaySeven {
+    const DAY: u8 = 7;
+    const PUZZLE_INPUT: &'static str = PUZZLE_INPUT;
 
-fn part_two(input: &str) -> u64 {
-    input
-        .par_lines()
-        .filter_map(|line| {
-            let mut parsed = parse_line(line);
+    type Input = Vec<Equation>;
+    type Answer = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.lines().map(|line| parse_line(input, line)).collect()
+    }
 
-            let values = parsed.split_off(1);
+    fn part_one(equations: &Self::Input) -> u64 {
+        sum_solvable(equations, 2)
+    }
+
+    fn part_two(equations: &Self::Input) -> u64 {
+        sum_solvable(equations, 3)
+    }
+}
 
-            let result = parsed.get(0).unwrap();
+/// A test value and the numbers to combine into it, never empty.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Equation {
+    result: u64,
+    values: Vec<u64>,
+}
 
-            if evaluate(result, &values, 3) {
-                return Some(*result);
-            } else {
-                return None;
-            }
-        })
+fn sum_solvable(equations: &[Equation], num_operations: u64) -> u64 {
+    equations
+        .par_iter()
+        .filter(|equation| evaluate(&equation.result, &equation.values, num_operations))
+        .map(|equation| equation.result)
         .sum()
 }
 
-fn parse_line(line: &str) -> Vec<u64> {
-    line.split(": ")
-        .flat_map(|part| {
-            part.split_whitespace()
-                .filter_map(|value| value.parse::<u64>().ok())
-        })
-        .collect()
+/// An equation such as `190: 10 19`.
+fn parse_line(input: &str, line: &str) -> Result<Equation, ParseError> {
+    let (result, values) = key_values(input, line, ":")?;
+    let values = list::<u64>(input, values, " ")?;
+
+    if values.is_empty() {
+        let end = line.trim_end();
+
+        return Err(ParseError::at(
+            input,
+            &end[end.len()..],
+            "expected at least one value",
+        ));
+    }
+
+    Ok(Equation {
+        result: number(input, result)?,
+        values,
+    })
 }
 
-fn evaluate(result: &u64, values: &Vec<u64>, num_operations: u64) -> bool {
+fn evaluate(result: &u64, values: &[u64], num_operations: u64) -> bool {
     let n = values.len();
 
     let number_of_combinations = num_operations.pow(n as u32 - 1);
@@ -83,7 +88,7 @@ fn evaluate(result: &u64, values: &Vec<u64>, num_operations: u64) -> bool {
         }
     }
 
-    return false;
+    false
 }
 
 fn do_operation(index: u64, lhs: u64, rhs: u64) -> u64 {
@@ -109,6 +114,8 @@ fn combine(lhs: u64, rhs: u64) -> u64 {
 
 #[cfg(test)]
 mod tests {
+    use super::DaySeven;
+    use crate::solution::Solution;
 
     const EXAMPLE_INPUT: &str = include_str!("./example_input.txt");
 
@@ -116,7 +123,7 @@ mod tests {
     fn part_one_example_returns_the_correct_answer() {
         let expected = 3749;
 
-        let actual = super::part_one(EXAMPLE_INPUT);
+        let actual = DaySeven::solve_part_one(EXAMPLE_INPUT);
 
         assert_eq!(actual, expected)
     }
@@ -125,8 +132,16 @@ mod tests {
     fn part_two_example_returns_the_correct_answer() {
         let expected = 11387;
 
-        let actual = super::part_two(EXAMPLE_INPUT);
+        let actual = DaySeven::solve_part_two(EXAMPLE_INPUT);
 
         assert_eq!(actual, expected)
     }
+
+    #[test]
+    fn equations_need_values() {
+        let err = DaySeven::parse("190: 10 19\n1: \n").unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 3));
+        assert_eq!(err.message, "expected at least one value");
+    }
 }
@@ -1,151 +1,141 @@
 use std::{
     collections::{HashMap, HashSet},
-    iter::FromIterator,
-    ops::{Add, Mul},
-    time::Instant,
+    str::FromStr,
 };
 
-use crate::models::{bounding_box::BoundingBox, point::Point};
-
-const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
+use crate::{
+    models::{
+        bounding_box::BoundingBox,
+        grid::Grid,
+        point::{Point, Position},
+    },
+    parse::ParseError,
+    solution::Solution,
+};
 
-pub(crate) fn run() {
-    println!("===== DAY EIGHT =====");
-    let now = Instant::now();
-    let part_one_result = part_one(PUZZLE_INPUT);
-    println!("({:.2?}) Part 1: {:?}", now.elapsed(), part_one_result);
-    let now = Instant::now();
-    let part_two_result = part_two(PUZZLE_INPUT);
-    println!("({:.2?}) Part 2: {:?}", now.elapsed(), part_two_result);
-}
+pub const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
 
-fn part_one(input: &str) -> usize {
-    // get height and width of the matrix
-    let width: usize = input.find('\n').unwrap() as usize;
-    let height: usize = (input.len()) as usize / width;
+pub struct DayEight;
 
-    let sanitized = input.replace('\n', "");
+impl Solution for DayEight {
+    const DAY: u8 = 8;
+    const PUZZLE_INPUT: &'static str = PUZZLE_INPUT;
 
-    // create a bounding box which goes from (0, 0) to (width -1, height -1)
-    let bounding_box = BoundingBox::new(Point::from((0, 0)), Point::from((width - 1, height - 1)));
+    type Input = AntennaMap;
+    type Answer = usize;
 
-    /*
-     * Set up some hash sets and maps.
-     * - One set for the position of all antennas
-     * - One map for the buckets of antennas + types
-     * - one set for the position of all anti-nodes
-     */
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        input.parse()
+    }
 
-    // Buckets for those antennas, so we can check _only_ antennas which would produce an anti-node
-    let mut antennas: HashMap<char, HashSet<Point>> = HashMap::new();
+    fn part_one(map: &Self::Input) -> usize {
+        // Positions of all anti-nodes
+        let mut antinode_positions: HashSet<Point> = HashSet::new();
+
+        // for each set of antennas for each antenna type
+        for coords in map.antennas.values() {
+            for (i, a) in coords.iter().enumerate() {
+                for b in coords.iter().skip(i + 1) {
+                    let d = if a < b { *b - *a } else { *a - *b };
+
+                    let (left_point, right_point) = if a < b {
+                        (*a - d, *b + d)
+                    } else {
+                        (*b - d, *a + d)
+                    };
+
+                    // handle left first
+                    if map.bounding_box.contains(left_point) {
+                        antinode_positions.insert(left_point);
+                    }
+
+                    if map.bounding_box.contains(right_point) {
+                        antinode_positions.insert(right_point);
+                    }
+                }
+            }
+        }
 
-    // Positions of all anti-nodes
-    let mut antinode_positions: HashSet<Point> = HashSet::new();
+        antinode_positions.len()
+    }
 
-    //parse all characters into their resepective buckets
+    fn part_two(map: &Self::Input) -> usize {
+        // Positions of all anti-nodes
+        let mut antinode_positions: HashSet<Point> = HashSet::new();
 
-    for (i, c) in sanitized.chars().enumerate() {
-        if c.is_alphanumeric() {
-            let col: usize = i as usize / width;
-            let row: usize = i as usize % width;
+        // for each set of antennas for each antenna type
+        for coords in map.antennas.values() {
+            for (i, a) in coords.iter().enumerate() {
+                for b in coords.iter().skip(i + 1) {
+                    let d = if a < b { *b - *a } else { *a - *b };
 
-            antennas
-                .entry(c)
-                .or_insert_with(HashSet::new)
-                .insert(Point::from((row, col)));
-        }
-    }
+                    let (mut upper, mut lower) = if a < b { (*a, *b) } else { (*b, *a) };
 
-    // for each set of antennas for each antenna type
-    for coords in antennas.values() {
-        for (i, a) in coords.iter().enumerate() {
-            for b in coords.iter().skip(i + 1) {
-                let d = if a < b { *b - *a } else { *a - *b };
-
-                let (left_point, right_point) = if a < b {
-                    (*a - d, *b + d)
-                } else {
-                    (*b - d, *a + d)
-                };
-
-                // handle left first
-                if bounding_box.contains(left_point) {
-                    antinode_positions.insert(left_point);
-                }
+                    while map.bounding_box.contains(upper) {
+                        antinode_positions.insert(upper);
+                        upper -= d;
+                    }
 
-                if bounding_box.contains(right_point) {
-                    antinode_positions.insert(right_point);
+                    while map.bounding_box.contains(lower) {
+                        antinode_positions.insert(lower);
+                        lower += d;
+                    }
                 }
             }
         }
-    }
 
-    antinode_positions.len()
+        antinode_positions.len()
+    }
 }
 
-fn part_two(input: &str) -> usize {
-    // get height and width of the matrix
-    let width: usize = input.find('\n').unwrap() as usize;
-    let height: usize = (input.len()) as usize / width;
-
-    let sanitized = input.replace('\n', "");
-
-    // create a bounding box which goes from (0, 0) to (width -1, height -1)
-    let bounding_box = BoundingBox::new(Point::from((0, 0)), Point::from((width - 1, height - 1)));
-
-    /*
-     * Set up some hash sets and maps.
-     * - One set for the position of all antennas
-     * - One map for the buckets of antennas + types
-     * - one set for the position of all anti-nodes
-     */
+pub struct AntennaMap {
+    bounding_box: BoundingBox,
 
     // Buckets for those antennas, so we can check _only_ antennas which would produce an anti-node
-    let mut antennas: HashMap<char, HashSet<Point>> = HashMap::new();
+    antennas: HashMap<char, HashSet<Point>>,
+}
 
-    // Positions of all anti-nodes
-    let mut antinode_positions: HashSet<Point> = HashSet::new();
+impl FromStr for AntennaMap {
+    type Err = ParseError;
 
-    //parse all characters into their resepective buckets
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        // Examples mark the antinodes with `#`, which are empty as far as the
+        // antennas are concerned.
+        let grid = Grid::try_parse(input, |c| {
+            (c == '.' || c == '#' || c.is_alphanumeric()).then_some(c)
+        })?;
 
-    for (i, c) in sanitized.chars().enumerate() {
-        if c.is_alphanumeric() {
-            let col: usize = i as usize / width;
-            let row: usize = i as usize % width;
+        // Every position in the map has to fit in a `Point`.
+        let Some(size) = Position::new(grid.width(), grid.height()).cast::<i32>() else {
+            return Err(ParseError::at(input, input, "the map is too large"));
+        };
 
-            antennas
-                .entry(c)
-                .or_insert_with(HashSet::new)
-                .insert(Point::from((row, col)));
-        }
-    }
+        // create a bounding box which goes from (0, 0) to (width -1, height -1)
+        let bounding_box = BoundingBox::new(Point::origin(), size - Point(1, 1));
 
-    // for each set of antennas for each antenna type
-    for coords in antennas.values() {
-        for (i, a) in coords.iter().enumerate() {
-            for b in coords.iter().skip(i + 1) {
-                let d = if a < b { *b - *a } else { *a - *b };
+        let mut antennas: HashMap<char, HashSet<Point>> = HashMap::new();
 
-                let (mut upper, mut lower) = if a < b { (*a, *b) } else { (*b, *a) };
+        //parse all characters into their resepective buckets
 
-                while bounding_box.contains(upper) {
-                    antinode_positions.insert(upper);
-                    upper -= d;
-                }
+        for (position, c) in grid.cells() {
+            if c.is_alphanumeric() {
+                let point = Point(position.x() as i32, position.y() as i32);
 
-                while bounding_box.contains(lower) {
-                    antinode_positions.insert(lower);
-                    lower += d;
-                }
+                antennas.entry(*c).or_default().insert(point);
             }
         }
-    }
 
-    antinode_positions.len()
+        Ok(Self {
+            bounding_box,
+            antennas,
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::DayEight;
+    use crate::solution::Solution;
 
     const EXAMPLE_INPUT: &str = include_str!("./example_input.txt");
 
@@ -153,7 +143,7 @@ mod tests {
     fn part_one_example_returns_the_correct_answer() {
         let expected = 14;
 
-        let actual = super::part_one(EXAMPLE_INPUT);
+        let actual = DayEight::solve_part_one(EXAMPLE_INPUT);
 
         assert_eq!(actual, expected)
     }
@@ -162,7 +152,7 @@ mod tests {
     fn part_two_example_returns_the_correct_answer() {
         let expected = 34;
 
-        let actual = super::part_two(EXAMPLE_INPUT);
+        let actual = DayEight::solve_part_two(EXAMPLE_INPUT);
 
         assert_eq!(actual, expected)
     }
@@ -171,7 +161,7 @@ mod tests {
     fn part_one_two_antennas() {
         let expected = 2;
 
-        let actual = super::part_one(
+        let actual = DayEight::solve_part_one(
             r"..........
 ...#......
 ..........
@@ -191,7 +181,7 @@ mod tests {
     fn part_one_with_3_antennas() {
         let expected = 4;
 
-        let actual = super::part_one(
+        let actual = DayEight::solve_part_one(
             r"..........
 ..........
 ..........
@@ -211,7 +201,7 @@ mod tests {
     fn part_one_with_4_antennas_with_1_different() {
         let expected = 4;
 
-        let actual = super::part_one(
+        let actual = DayEight::solve_part_one(
             r"..........
 ..........
 ..........
@@ -231,7 +221,7 @@ mod tests {
     fn part_two_with_3_antennas() {
         let expected = 9;
 
-        let actual = super::part_two(
+        let actual = DayEight::solve_part_two(
             r"T.........
 ...T......
 .T........
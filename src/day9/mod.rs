@@ -1,80 +1,86 @@
-use core::num;
-use std::{borrow::BorrowMut, mem, time::Instant};
-
-const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
-
-pub(crate) fn run() {
-    println!("===== DAY NINE =====");
-    let now = Instant::now();
-    let part_one_result = part_one(PUZZLE_INPUT);
-    println!("({:.2?}) Part 1: {:?}", now.elapsed(), part_one_result);
-    let now = Instant::now();
-    let part_two_result = part_two(PUZZLE_INPUT);
-    println!("({:.2?}) Part 2: {:?}", now.elapsed(), part_two_result);
-}
+use crate::{
+    parse::{digits, ParseError},
+    solution::Solution,
+};
 
-fn part_one(input: &str) -> u64 {
-    let mut memory_map = get_memory_map(input);
+pub const PUZZLE_INPUT: &str = include_str!("./puzzle_input.txt");
 
-    let mut i: usize = 0;
-    let mut j: usize = memory_map.len() - 1;
+pub struct DayNine;
 
-    while i < j {
-        while i < j && memory_map[i].is_some() {
-            i += 1;
-        }
+impl Solution for DayNine {
+    const DAY: u8 = 9;
+    const PUZZLE_INPUT: &'static str = PUZZLE_INPUT;
 
-        while j > i && memory_map[j].is_none() {
-            j -= 1;
-        }
+    type Input = Vec<Option<u64>>;
+    type Answer = u64;
 
-        if i < j {
-            memory_map.swap(i, j);
-            i += 1;
-            j -= 1;
-        }
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        get_memory_map(input)
     }
 
-    memory_map
-        .iter()
-        .enumerate()
-        .filter_map(|(i, node)| node.map(|n| n * i as u64))
-        .sum()
-}
+    fn part_one(memory_map: &Self::Input) -> u64 {
+        let mut memory_map = memory_map.clone();
 
-fn part_two(input: &str) -> u64 {
-    let mut memory_map = get_memory_map(input);
+        let mut i: usize = 0;
+        let mut j: usize = memory_map.len() - 1;
 
-    let blocks = get_blocks_from_memory_map(&memory_map);
+        while i < j {
+            while i < j && memory_map[i].is_some() {
+                i += 1;
+            }
 
-    let mut empty_blocks = blocks
-        .iter()
-        .filter(|block| block.id().is_none())
-        .copied()
-        .collect::<Vec<FileBlock>>();
-    let filled_blocks = blocks
-        .iter()
-        .filter(|block| block.id().is_some())
-        .rev()
-        .copied()
-        .collect::<Vec<FileBlock>>();
-
-    for filled_block in filled_blocks {
-        for empty_block in &mut empty_blocks {
-            if empty_block.size() >= filled_block.size()
-                && empty_block.start() < filled_block.start()
-            {
-                for j in 0..filled_block.size() {
-                    memory_map[empty_block.start() + j] = filled_block.id();
-                    memory_map[filled_block.start() + j] = None;
-                }
+            while j > i && memory_map[j].is_none() {
+                j -= 1;
+            }
 
-                empty_block.decrease_size(filled_block.size());
-                break;
+            if i < j {
+                memory_map.swap(i, j);
+                i += 1;
+                j -= 1;
             }
         }
+
+        checksum(&memory_map)
     }
 
+    fn part_two(memory_map: &Self::Input) -> u64 {
+        let mut memory_map = memory_map.clone();
+
+        let blocks = get_blocks_from_memory_map(&memory_map);
+
+        let mut empty_blocks = blocks
+            .iter()
+            .filter(|block| block.id().is_none())
+            .copied()
+            .collect::<Vec<FileBlock>>();
+        let filled_blocks = blocks
+            .iter()
+            .filter(|block| block.id().is_some())
+            .rev()
+            .copied()
+            .collect::<Vec<FileBlock>>();
+
+        for filled_block in filled_blocks {
+            for empty_block in &mut empty_blocks {
+                if empty_block.size() >= filled_block.size()
+                    && empty_block.start() < filled_block.start()
+                {
+                    for j in 0..filled_block.size() {
+                        memory_map[empty_block.start() + j] = filled_block.id();
+                        memory_map[filled_block.start() + j] = None;
+                    }
+
+                    empty_block.decrease_size(filled_block.size());
+                    break;
+                }
+            }
+        }
+
+        checksum(&memory_map)
+    }
+}
+
+fn checksum(memory_map: &[Option<u64>]) -> u64 {
     memory_map
         .iter()
         .enumerate()
@@ -82,27 +88,27 @@ fn part_two(input: &str) -> u64 {
         .sum()
 }
 
-fn get_memory_map(input: &str) -> Vec<Option<u64>> {
-    input
-        .chars()
+fn get_memory_map(input: &str) -> Result<Vec<Option<u64>>, ParseError> {
+    let disk_map = input.trim_end();
+    let block_sizes = digits(input, disk_map)?;
+
+    if block_sizes.iter().all(|block_size| *block_size == 0) {
+        return Err(ParseError::at(
+            input,
+            disk_map,
+            "the disk map has no blocks",
+        ));
+    }
+
+    Ok(block_sizes
+        .iter()
         .enumerate()
-        .scan(0u64, |current_id, (i, c)| {
-            let file_block_size = c
-                .to_string()
-                .parse::<usize>()
-                .expect("failed to parse file block size");
-
-            let block_iter = if i % 2 == 0 {
-                let id = *current_id;
-                *current_id += 1;
-                std::iter::repeat(Some(id)).take(file_block_size)
-            } else {
-                std::iter::repeat(None).take(file_block_size)
-            };
-            Some(block_iter)
+        .flat_map(|(i, block_size)| {
+            let block = (i % 2 == 0).then_some(i as u64 / 2);
+
+            std::iter::repeat_n(block, *block_size as usize)
         })
-        .flatten()
-        .collect()
+        .collect())
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -135,7 +141,7 @@ impl FileBlock {
     }
 }
 
-fn get_blocks_from_memory_map(memory_map: &Vec<Option<u64>>) -> Vec<FileBlock> {
+fn get_blocks_from_memory_map(memory_map: &[Option<u64>]) -> Vec<FileBlock> {
     let mut file_blocks: Vec<FileBlock> = Vec::new();
 
     let mut file_block: Option<FileBlock> = None;
@@ -161,6 +167,8 @@ fn get_blocks_from_memory_map(memory_map: &Vec<Option<u64>>) -> Vec<FileBlock> {
 
 #[cfg(test)]
 mod tests {
+    use super::DayNine;
+    use crate::solution::Solution;
 
     const EXAMPLE_INPUT: &str = include_str!("./example_input.txt");
 
@@ -168,7 +176,7 @@ mod tests {
     fn part_one_example_returns_the_correct_answer() {
         let expected = 1928;
 
-        let actual = super::part_one(EXAMPLE_INPUT);
+        let actual = DayNine::solve_part_one(EXAMPLE_INPUT);
 
         assert_eq!(actual, expected)
     }
@@ -177,7 +185,7 @@ mod tests {
     fn part_one_simple() {
         let expected = 60;
 
-        let actual = super::part_one("12345");
+        let actual = DayNine::solve_part_one("12345");
 
         assert_eq!(actual, expected);
     }
@@ -186,8 +194,18 @@ mod tests {
     fn part_two_example_returns_the_correct_answer() {
         let expected = 2858;
 
-        let actual = super::part_two(EXAMPLE_INPUT);
+        let actual = DayNine::solve_part_two(EXAMPLE_INPUT);
 
         assert_eq!(actual, expected)
     }
+
+    #[test]
+    fn disk_map_needs_blocks() {
+        for input in ["", "\n", "000"] {
+            let err = DayNine::parse(input).unwrap_err();
+
+            assert_eq!((err.line, err.column), (1, 1));
+            assert_eq!(err.message, "the disk map has no blocks");
+        }
+    }
 }
@@ -0,0 +1,26 @@
+use crate::{
+    day1::DayOne, day10::DayTen, day11::DayEleven, day2::DayTwo, day3::DayThree, day4::DayFour,
+    day5::DayFive, day6::DaySix, day7::DaySeven, day8::DayEight, day9::DayNine, solution::Puzzle,
+};
+
+/// Every solved day, in order.
+pub const DAYS: [&dyn Puzzle; 11] = [
+    &DayOne, &DayTwo, &DayThree, &DayFour, &DayFive, &DaySix, &DaySeven, &DayEight, &DayNine,
+    &DayTen, &DayEleven,
+];
+
+pub fn find(day: u8) -> Option<&'static dyn Puzzle> {
+    DAYS.iter().copied().find(|puzzle| puzzle.day() == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DAYS;
+
+    #[test]
+    fn days_are_registered_in_order_without_gaps() {
+        for (i, puzzle) in DAYS.iter().enumerate() {
+            assert_eq!(puzzle.day() as usize, i + 1);
+        }
+    }
+}
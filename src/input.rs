@@ -0,0 +1,110 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::solution::Puzzle;
+
+/// Where a day's puzzle input is read from when running it.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub enum InputSource {
+    /// The `puzzle_input.txt` compiled into the binary.
+    #[default]
+    Embedded,
+    /// A single file, only valid when running one day.
+    File(PathBuf),
+    /// Standard input, only valid when running one day.
+    Stdin,
+    /// A directory holding one `day<N>.txt` per day.
+    Directory(PathBuf),
+}
+
+impl InputSource {
+    pub fn from_arg(value: &str) -> Self {
+        match value {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+
+    /// Whether the source can only feed a single day.
+    pub fn is_single(&self) -> bool {
+        matches!(self, InputSource::File(_) | InputSource::Stdin)
+    }
+
+    pub fn load(&self, puzzle: &dyn Puzzle) -> io::Result<String> {
+        let raw = match self {
+            InputSource::Embedded => puzzle.puzzle_input().to_string(),
+            InputSource::File(path) => read(path)?,
+            InputSource::Stdin => {
+                let mut buffer = String::new();
+                io::stdin().read_to_string(&mut buffer)?;
+                buffer
+            }
+            InputSource::Directory(dir) => read(&dir.join(format!("day{}.txt", puzzle.day())))?,
+        };
+
+        Ok(normalize(&raw))
+    }
+
+    /// Loads the day's input, with the day in the error message.
+    pub(crate) fn load_day(&self, puzzle: &dyn Puzzle) -> Result<String, String> {
+        self.load(puzzle)
+            .map_err(|err| format!("failed to read input for day {}: {}", puzzle.day(), err))
+    }
+}
+
+/// Reads a file, naming it in the error since `io::Error` doesn't.
+fn read(path: &Path) -> io::Result<String> {
+    fs::read_to_string(path)
+        .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))
+}
+
+/// Converts CRLF line endings and drops trailing newlines so that inputs saved
+/// by editors or downloaded from the site parse the same as the embedded ones.
+fn normalize(raw: &str) -> String {
+    raw.replace("\r\n", "\n").trim_end_matches('\n').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::PathBuf};
+
+    use super::{normalize, InputSource};
+    use crate::day1::DayOne;
+
+    #[test]
+    fn normalize_strips_trailing_newlines_and_carriage_returns() {
+        assert_eq!(normalize("1 2\r\n3 4\r\n\n"), "1 2\n3 4");
+    }
+
+    #[test]
+    fn dash_means_stdin() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("input.txt"),
+            InputSource::File(PathBuf::from("input.txt"))
+        );
+    }
+
+    #[test]
+    fn directory_uses_day_file() {
+        let dir = std::env::temp_dir().join(format!("aoc-2024-inputs-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let source = InputSource::Directory(dir.clone());
+
+        let err = source.load(&DayOne).unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains(&dir.join("day1.txt").display().to_string()));
+
+        fs::write(dir.join("day1.txt"), "3 4\n4 3\n").unwrap();
+
+        assert_eq!(source.load(&DayOne).unwrap(), "3 4\n4 3");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
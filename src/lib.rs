@@ -0,0 +1,26 @@
+//! Advent of Code 2024 solutions.
+//!
+//! Every day implements [`solution::Solution`] and is registered in
+//! [`days::DAYS`], so callers can run, verify or benchmark days without
+//! knowing their input or answer types. The grid and coordinate types the
+//! solutions share live in [`models`].
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+pub mod days;
+pub mod input;
+pub mod models;
+pub mod parse;
+pub mod runner;
+pub mod solution;
+pub mod verify;
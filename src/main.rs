@@ -1,18 +1,77 @@
-#![allow(warnings)]
-mod day1;
-mod day10;
-mod day11;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-
-mod models;
-
-fn main() {
-    day11::run();
+mod cli;
+
+use std::process::ExitCode;
+
+use aoc_2024::{
+    days, runner,
+    solution::{Part, Puzzle},
+    verify,
+};
+use cli::{Command, Selection};
+
+fn main() -> ExitCode {
+    let command = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, cli::USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let result = execute(command);
+
+    time_it::report_span_summary();
+
+    match result {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn execute(command: Command) -> Result<ExitCode, String> {
+    match command {
+        Command::Help => println!("{}", cli::USAGE),
+        Command::List => {
+            for puzzle in days::DAYS {
+                println!("Day {}", puzzle.day());
+            }
+        }
+        Command::Run {
+            selection,
+            part,
+            input,
+        } => {
+            let parts = match part {
+                Some(part) => vec![part],
+                None => Part::ALL.to_vec(),
+            };
+
+            runner::run_all(&select(selection)?, &input, &parts)?;
+        }
+        Command::Verify {
+            selection,
+            answers,
+            input,
+        } => {
+            let answers = verify::load_answers(answers.as_deref())?;
+
+            if verify::verify_all(&select(selection)?, &input, &answers).has_failures() {
+                return Ok(ExitCode::FAILURE);
+            }
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+fn select(selection: Selection) -> Result<Vec<&'static dyn Puzzle>, String> {
+    match selection {
+        Selection::All => Ok(days::DAYS.to_vec()),
+        Selection::Day(number) => days::find(number)
+            .map(|puzzle| vec![puzzle])
+            .ok_or_else(|| format!("day {} has not been solved yet", number)),
+    }
 }
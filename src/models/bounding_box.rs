@@ -1,17 +1,26 @@
-use super::point::Point;
+use super::point::{Coordinate, Point};
 
-#[derive(Debug)]
-pub struct BoundingBox {
-    tl: Point,
-    br: Point,
+/// Inclusive rectangle between a top left and a bottom right corner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoundingBox<T = i32> {
+    tl: Point<T>,
+    br: Point<T>,
 }
 
-impl BoundingBox {
-    pub fn new(tl: Point, br: Point) -> Self {
+impl<T: Coordinate> BoundingBox<T> {
+    pub fn new(tl: Point<T>, br: Point<T>) -> Self {
         Self { tl, br }
     }
 
-    pub fn contains(&self, point: Point) -> bool {
+    pub fn top_left(&self) -> Point<T> {
+        self.tl
+    }
+
+    pub fn bottom_right(&self) -> Point<T> {
+        self.br
+    }
+
+    pub fn contains(&self, point: Point<T>) -> bool {
         point.x() >= self.tl.x()
             && point.x() <= self.br.x()
             && point.y() >= self.tl.y()
@@ -0,0 +1,245 @@
+//! Path counting and enumeration on directed acyclic graphs.
+//!
+//! Like [`search`](super::search), the graph is given as a closure returning
+//! the successors of a node. Paths start at a source and end at the first node
+//! matching `is_sink`, sinks aren't expanded any further.
+
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+    vec,
+};
+
+/// Counts paths by dynamic programming, remembering the count of every node it
+/// has seen, so counting from many sources in the same graph stays linear in
+/// the size of the graph.
+pub struct PathCounter<N, F, S> {
+    successors: F,
+    is_sink: S,
+    counts: HashMap<N, u64>,
+}
+
+impl<N, F, I, S> PathCounter<N, F, S>
+where
+    N: Eq + Hash + Clone,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    S: FnMut(&N) -> bool,
+{
+    pub fn new(successors: F, is_sink: S) -> Self {
+        Self {
+            successors,
+            is_sink,
+            counts: HashMap::new(),
+        }
+    }
+
+    /// Number of distinct paths from `source` to any sink.
+    ///
+    /// Panics when a cycle is reachable from `source`.
+    pub fn count(&mut self, source: &N) -> u64 {
+        // Explicit stack rather than recursion, long chains would otherwise
+        // overflow the call stack. A node is pushed once to expand it and once
+        // more, with its successors, to add their counts up.
+        let mut stack: Vec<(N, Option<Vec<N>>)> = vec![(source.clone(), None)];
+        let mut in_progress = HashSet::new();
+
+        while let Some((node, successors)) = stack.pop() {
+            match successors {
+                None => {
+                    if self.counts.contains_key(&node) {
+                        continue;
+                    }
+
+                    if (self.is_sink)(&node) {
+                        self.counts.insert(node, 1);
+                        continue;
+                    }
+
+                    assert!(in_progress.insert(node.clone()), "the graph has a cycle");
+
+                    let next = (self.successors)(&node).into_iter().collect::<Vec<N>>();
+                    let pending = next
+                        .iter()
+                        .filter(|next| !self.counts.contains_key(*next))
+                        .cloned()
+                        .map(|next| (next, None))
+                        .collect::<Vec<_>>();
+
+                    stack.push((node, Some(next)));
+                    stack.extend(pending);
+                }
+                Some(next) => {
+                    let count = next.iter().map(|next| self.counts[next]).sum();
+
+                    in_progress.remove(&node);
+                    self.counts.insert(node, count);
+                }
+            }
+        }
+
+        self.counts[source]
+    }
+}
+
+/// Number of distinct paths from any of the `sources` to any sink.
+pub fn count_paths<N, F, I>(
+    sources: impl IntoIterator<Item = N>,
+    successors: F,
+    is_sink: impl FnMut(&N) -> bool,
+) -> u64
+where
+    N: Eq + Hash + Clone,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    let mut counter = PathCounter::new(successors, is_sink);
+
+    sources
+        .into_iter()
+        .map(|source| counter.count(&source))
+        .sum()
+}
+
+/// Every path from `source` to a sink, produced lazily in depth first order.
+/// There can be exponentially many, use [`count_paths`] to only count them.
+pub fn paths<N, F, I, S>(source: N, successors: F, is_sink: S) -> Paths<N, F, S>
+where
+    N: Clone,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    S: FnMut(&N) -> bool,
+{
+    Paths {
+        successors,
+        is_sink,
+        path: Vec::new(),
+        // The bottom frame stands for a virtual root whose only successor is
+        // the source, so the source is handled like any other node.
+        stack: vec![vec![source].into_iter()],
+    }
+}
+
+pub struct Paths<N, F, S> {
+    successors: F,
+    is_sink: S,
+    /// The nodes leading to the top frame of `stack`.
+    path: Vec<N>,
+    /// Successors of each node on `path` which are still to be explored.
+    stack: Vec<vec::IntoIter<N>>,
+}
+
+impl<N, F, I, S> Iterator for Paths<N, F, S>
+where
+    N: Clone,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    S: FnMut(&N) -> bool,
+{
+    type Item = Vec<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let Some(node) = self.stack.last_mut()?.next() else {
+                self.stack.pop();
+                self.path.pop();
+                continue;
+            };
+
+            if (self.is_sink)(&node) {
+                let mut path = self.path.clone();
+                path.push(node);
+
+                return Some(path);
+            }
+
+            let successors = (self.successors)(&node).into_iter().collect::<Vec<N>>();
+
+            self.path.push(node);
+            self.stack.push(successors.into_iter());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{count_paths, paths, PathCounter};
+
+    fn diamond() -> HashMap<char, Vec<char>> {
+        HashMap::from([
+            ('a', vec!['b', 'c']),
+            ('b', vec!['d']),
+            ('c', vec!['d', 'e']),
+            ('d', vec!['e']),
+            ('e', vec![]),
+        ])
+    }
+
+    #[test]
+    fn counts_paths_to_sinks() {
+        let graph = diamond();
+
+        let expected = 3;
+        let actual = count_paths(['a'], |n| graph[n].clone(), |n| *n == 'e');
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn enumerates_the_same_paths() {
+        let graph = diamond();
+
+        let expected = vec![
+            vec!['a', 'b', 'd', 'e'],
+            vec!['a', 'c', 'd', 'e'],
+            vec!['a', 'c', 'e'],
+        ];
+        let actual = paths('a', |n| graph[n].clone(), |n| *n == 'e').collect::<Vec<_>>();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn source_can_be_a_sink() {
+        let expected = vec![vec![1]];
+        let actual = paths(1, |_| vec![2], |n| *n == 1).collect::<Vec<_>>();
+
+        assert_eq!(actual, expected);
+        assert_eq!(count_paths([1], |_| vec![2], |n| *n == 1), 1);
+    }
+
+    #[test]
+    fn dead_ends_have_no_paths() {
+        assert_eq!(
+            count_paths([0], |n| vec![n + 1], |n| *n == 10 || *n == 5),
+            1
+        );
+        assert_eq!(count_paths([0], |_: &i32| vec![], |n| *n == 10), 0);
+    }
+
+    #[test]
+    fn counting_is_memoised() {
+        // Each layer has two nodes which both lead to both nodes of the next
+        // layer, so there are 2^60 paths through 60 layers.
+        let successors = |(layer, _): &(u32, u32)| vec![(layer + 1, 0), (layer + 1, 1)];
+        let mut counter = PathCounter::new(successors, |(layer, _): &(u32, u32)| *layer == 60);
+
+        assert_eq!(counter.count(&(0, 0)), 1 << 60);
+        assert_eq!(counter.count(&(1, 1)), 1 << 59);
+
+        // Enumeration is lazy, the first path doesn't need all of them.
+        let first = paths((0, 0), successors, |(layer, _)| *layer == 60)
+            .next()
+            .unwrap();
+
+        assert_eq!(first.len(), 61);
+    }
+
+    #[test]
+    #[should_panic(expected = "the graph has a cycle")]
+    fn cycles_are_reported() {
+        count_paths([0], |n| vec![(n + 1) % 3], |n| *n == 5);
+    }
+}
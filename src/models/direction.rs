@@ -0,0 +1,160 @@
+use super::point::{Coordinate, Point};
+
+/// A compass heading on a grid where north is up, i.e. towards smaller `y`.
+///
+/// The variants go clockwise in steps of 45°, the four cardinal ones are what
+/// most puzzles need, the diagonal ones are there for 8-way movement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// The 4-way headings, clockwise from north.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// The 8-way headings, clockwise from north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    pub fn is_cardinal(self) -> bool {
+        (self as usize).is_multiple_of(2)
+    }
+
+    /// Turns clockwise by `steps` of 45°, counter-clockwise when negative.
+    pub fn rotate(self, steps: i32) -> Direction {
+        Self::ALL[(self as i32 + steps).rem_euclid(8) as usize]
+    }
+
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.rotate(-2)
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.rotate(4)
+    }
+
+    /// The step to the next cell in this direction.
+    pub const fn offset(self) -> Point {
+        match self {
+            Direction::North => Point(0, -1),
+            Direction::NorthEast => Point(1, -1),
+            Direction::East => Point(1, 0),
+            Direction::SouthEast => Point(1, 1),
+            Direction::South => Point(0, 1),
+            Direction::SouthWest => Point(-1, 1),
+            Direction::West => Point(-1, 0),
+            Direction::NorthWest => Point(-1, -1),
+        }
+    }
+
+    /// The heading of a unit offset, `None` for any other offset.
+    pub fn from_offset<C: Coordinate>(offset: Point<C>) -> Option<Direction> {
+        let offset = offset.cast::<i32>()?;
+
+        Self::ALL
+            .into_iter()
+            .find(|direction| direction.offset() == offset)
+    }
+
+    /// Reads an arrow (`^>v<`), a compass letter (`NESW`) or a move (`UDLR`).
+    pub fn parse(c: char) -> Option<Direction> {
+        match c {
+            '^' | 'N' | 'U' => Some(Direction::North),
+            '>' | 'E' | 'R' => Some(Direction::East),
+            'v' | 'S' | 'D' => Some(Direction::South),
+            '<' | 'W' | 'L' => Some(Direction::West),
+            _ => None,
+        }
+    }
+}
+
+impl From<Direction> for Point {
+    fn from(value: Direction) -> Self {
+        value.offset()
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        Direction::parse(value).ok_or(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::point::Point;
+
+    use super::Direction;
+
+    #[test]
+    fn turning() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::SouthWest.turn_right(), Direction::NorthWest);
+        assert_eq!(Direction::East.reverse(), Direction::West);
+        assert_eq!(Direction::NorthEast.reverse(), Direction::SouthWest);
+    }
+
+    #[test]
+    fn rotate_wraps_in_both_directions() {
+        assert_eq!(Direction::North.rotate(1), Direction::NorthEast);
+        assert_eq!(Direction::North.rotate(-1), Direction::NorthWest);
+        assert_eq!(Direction::West.rotate(10), Direction::North);
+        assert_eq!(Direction::East.rotate(-16), Direction::East);
+    }
+
+    #[test]
+    fn offsets_round_trip() {
+        for direction in Direction::ALL {
+            assert_eq!(Direction::from_offset(direction.offset()), Some(direction));
+        }
+
+        assert_eq!(Direction::from_offset(Point(2, 0)), None);
+        assert_eq!(Direction::from_offset(Point(0, 0)), None);
+    }
+
+    #[test]
+    fn cardinal_directions() {
+        assert!(Direction::CARDINAL.iter().all(|d| d.is_cardinal()));
+        assert!(!Direction::SouthEast.is_cardinal());
+    }
+
+    #[test]
+    fn parse_arrows_letters_and_moves() {
+        let expected = Direction::CARDINAL.repeat(3);
+        let actual = "^>v<NESWURDL"
+            .chars()
+            .map(|c| Direction::parse(c).unwrap())
+            .collect::<Vec<Direction>>();
+
+        assert_eq!(actual, expected);
+        assert_eq!(Direction::try_from('#'), Err('#'));
+    }
+}
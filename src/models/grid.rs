@@ -1 +1,472 @@
+use std::{
+    iter::StepBy,
+    ops::{Index, IndexMut},
+    slice::{ChunksExact, Iter},
+    str::FromStr,
+};
 
+use super::{
+    bounding_box::BoundingBox,
+    direction::Direction,
+    point::{Coordinate, Point, Position},
+};
+use crate::parse::{self, ParseError};
+
+/// Offsets to the cells sharing an edge, clockwise from north.
+pub const ORTHOGONAL: [Point; 4] = [
+    Direction::North.offset(),
+    Direction::East.offset(),
+    Direction::South.offset(),
+    Direction::West.offset(),
+];
+
+/// Offsets to the cells only sharing a corner, clockwise from north east.
+pub const DIAGONAL: [Point; 4] = [
+    Direction::NorthEast.offset(),
+    Direction::SouthEast.offset(),
+    Direction::SouthWest.offset(),
+    Direction::NorthWest.offset(),
+];
+
+/// Offsets to all eight surrounding cells, clockwise from north west.
+pub const ADJACENT: [Point; 8] = [
+    Direction::NorthWest.offset(),
+    Direction::North.offset(),
+    Direction::NorthEast.offset(),
+    Direction::East.offset(),
+    Direction::SouthEast.offset(),
+    Direction::South.offset(),
+    Direction::SouthWest.offset(),
+    Direction::West.offset(),
+];
+
+/// A rectangular grid stored row by row in a single `Vec`.
+///
+/// Cells can be looked up with any kind of [`Point`], points outside of the
+/// grid, including negative ones, are simply not found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from cells in row-major order.
+    ///
+    /// Panics when the number of cells isn't a multiple of `width`.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "{} cells don't fit in rows of {}",
+            cells.len(),
+            width
+        );
+
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses one cell per character, with one row per line.
+    ///
+    /// Panics when the lines aren't all the same length.
+    pub fn parse(input: &str, mut cell: impl FnMut(char) -> T) -> Self {
+        Self::try_parse(input, |c| Some(cell(c))).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Like [`Grid::parse`], but `cell` can reject a character by returning
+    /// `None`, and ragged lines are an error rather than a panic.
+    pub fn try_parse(input: &str, cell: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        parse::grid(input, input, cell)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Box from the origin to the bottom right cell, or `None` for an empty
+    /// grid.
+    pub fn bounding_box(&self) -> Option<BoundingBox<usize>> {
+        if self.is_empty() {
+            return None;
+        }
+
+        Some(BoundingBox::new(
+            Position::origin(),
+            Position::new(self.width - 1, self.height - 1),
+        ))
+    }
+
+    fn index_of<C: Coordinate>(&self, point: Point<C>) -> Option<usize> {
+        let Point(x, y) = point.cast::<usize>()?;
+
+        (x < self.width && y < self.height).then(|| y * self.width + x)
+    }
+
+    fn position_of(&self, index: usize) -> Position {
+        Position::new(index % self.width, index / self.width)
+    }
+
+    pub fn contains<C: Coordinate>(&self, point: Point<C>) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    pub fn get<C: Coordinate>(&self, point: Point<C>) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut<C: Coordinate>(&mut self, point: Point<C>) -> Option<&mut T> {
+        self.index_of(point).map(|i| &mut self.cells[i])
+    }
+
+    /// Cells in row-major order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// Cells in row-major order, along with their position.
+    pub fn cells(&self) -> impl Iterator<Item = (Position, &T)> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.position_of(i), cell))
+    }
+
+    /// Every position in the grid, in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> + '_ {
+        (0..self.cells.len()).map(|i| self.position_of(i))
+    }
+
+    /// Positions of the cells matching `predicate`, in row-major order.
+    pub fn find_all<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Position> + 'a {
+        self.cells()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(position, _)| position)
+    }
+
+    /// Positions of the cells equal to `value`, in row-major order.
+    pub fn positions_of<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Position> + 'a
+    where
+        T: PartialEq,
+    {
+        self.find_all(move |cell| cell == value)
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> ChunksExact<'_, T> {
+        // `chunks_exact` doesn't accept 0, an empty grid has no rows anyway.
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> Option<StepBy<Iter<'_, T>>> {
+        // A grid without rows can still have a width.
+        (x < self.width && self.height > 0).then(|| self.cells[x..].iter().step_by(self.width))
+    }
+
+    /// Cells at each of the `offsets` from `from`, skipping the ones outside
+    /// of the grid unless [`Neighbours::wrapping`] is used.
+    pub fn neighbours<'a, C: Coordinate>(
+        &'a self,
+        from: Point<C>,
+        offsets: &'a [Point],
+    ) -> Neighbours<'a, T> {
+        Neighbours {
+            grid: self,
+            from: Point(from.0.to_i128(), from.1.to_i128()),
+            offsets: offsets.iter(),
+            wrapping: false,
+        }
+    }
+
+    pub fn orthogonal_neighbours<C: Coordinate>(&self, from: Point<C>) -> Neighbours<'_, T> {
+        self.neighbours(from, &ORTHOGONAL)
+    }
+
+    pub fn diagonal_neighbours<C: Coordinate>(&self, from: Point<C>) -> Neighbours<'_, T> {
+        self.neighbours(from, &DIAGONAL)
+    }
+
+    pub fn adjacent_neighbours<C: Coordinate>(&self, from: Point<C>) -> Neighbours<'_, T> {
+        self.neighbours(from, &ADJACENT)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+/// Iterator over the `(position, cell)` pairs around a point, see
+/// [`Grid::neighbours`].
+#[derive(Debug, Clone)]
+pub struct Neighbours<'a, T> {
+    grid: &'a Grid<T>,
+    from: Point<i128>,
+    offsets: Iter<'a, Point>,
+    wrapping: bool,
+}
+
+impl<T> Neighbours<'_, T> {
+    /// Treats the grid as a torus, so stepping off one edge comes back in on
+    /// the opposite one.
+    pub fn wrapping(mut self) -> Self {
+        self.wrapping = true;
+        self
+    }
+}
+
+impl<'a, T> Iterator for Neighbours<'a, T> {
+    type Item = (Position, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let grid = self.grid;
+
+        if grid.is_empty() {
+            return None;
+        }
+
+        for offset in self.offsets.by_ref() {
+            let mut point = Point(
+                self.from.0 + offset.0 as i128,
+                self.from.1 + offset.1 as i128,
+            );
+
+            if self.wrapping {
+                point = Point(
+                    point.0.rem_euclid(grid.width as i128),
+                    point.1.rem_euclid(grid.height as i128),
+                );
+            }
+
+            if let Some(i) = grid.index_of(point) {
+                return Some((grid.position_of(i), &grid.cells[i]));
+            }
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.offsets.len()))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+}
+
+impl From<&str> for Grid<char> {
+    fn from(value: &str) -> Self {
+        Self::parse(value, |c| c)
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_parse(s, Some)
+    }
+}
+
+impl<T, C: Coordinate> Index<Point<C>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<C>) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("{} is outside of the grid", point))
+    }
+}
+
+impl<T, C: Coordinate> IndexMut<Point<C>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<C>) -> &mut Self::Output {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("{} is outside of the grid", point))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::point::{Point, Position};
+
+    use super::{Grid, ADJACENT};
+
+    const EXAMPLE_INPUT: &str = "abc\ndef";
+
+    #[test]
+    fn parse_maps_every_character() {
+        let grid = Grid::parse("12\n34\n", |c| c.to_digit(10).unwrap());
+
+        assert_eq!(grid.width(), 2);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.iter().sum::<u32>(), 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "line 2, column 2: 1 wide instead of 2")]
+    fn parse_rejects_ragged_lines() {
+        Grid::parse("ab\nc", |c| c);
+    }
+
+    #[test]
+    fn try_parse_reports_where_it_failed() {
+        let err = Grid::try_parse("12\n3x", |c| c.to_digit(10)).unwrap_err();
+
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 2, "x"));
+
+        let err = "ab\nabc".parse::<Grid<char>>().unwrap_err();
+
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "c"));
+    }
+
+    #[test]
+    fn get_is_bounds_checked() {
+        let grid = Grid::from(EXAMPLE_INPUT);
+
+        assert_eq!(grid.get(Position::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.get(Point(1, 0)), Some(&'b'));
+        assert_eq!(grid.get(Point(-1, 0)), None);
+        assert_eq!(grid.get(Position::new(3, 0)), None);
+        assert_eq!(grid.get(Position::new(0, 2)), None);
+    }
+
+    #[test]
+    fn get_mut_updates_the_cell() {
+        let mut grid = Grid::from(EXAMPLE_INPUT);
+
+        *grid.get_mut(Point(0, 1)).unwrap() = 'x';
+        grid[Position::new(1, 1)] = 'y';
+
+        let expected = vec!['a', 'b', 'c', 'x', 'y', 'f'];
+        let actual = grid.iter().copied().collect::<Vec<char>>();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn positions_of_are_in_row_major_order() {
+        let grid = Grid::from("#.#\n.#.");
+
+        let expected = vec![
+            Position::new(0, 0),
+            Position::new(2, 0),
+            Position::new(1, 1),
+        ];
+        let actual = grid.positions_of(&'#').collect::<Vec<Position>>();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid::from(EXAMPLE_INPUT);
+
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).unwrap().copied().collect::<String>(), "be");
+        assert!(grid.column(3).is_none());
+    }
+
+    #[test]
+    fn empty_grid() {
+        let grid = Grid::from("");
+
+        assert!(grid.is_empty());
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.bounding_box(), None);
+        assert_eq!(grid.get(Point(0, 0)), None);
+    }
+
+    #[test]
+    fn grid_without_rows_has_no_columns() {
+        let grid = Grid::<char>::from_vec(3, vec![]);
+
+        assert!(grid.column(1).is_none());
+    }
+
+    #[test]
+    fn orthogonal_neighbours_skip_the_edges() {
+        let grid = Grid::from(EXAMPLE_INPUT);
+
+        let expected = vec![(Position::new(1, 0), &'b'), (Position::new(0, 1), &'d')];
+        let actual = grid.orthogonal_neighbours(Point(0, 0)).collect::<Vec<_>>();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn diagonal_and_adjacent_neighbours() {
+        let grid = Grid::from("abc\ndef\nghi");
+
+        let diagonal = grid
+            .diagonal_neighbours(Position::new(1, 1))
+            .map(|(_, c)| *c)
+            .collect::<String>();
+        let adjacent = grid
+            .adjacent_neighbours(Position::new(1, 1))
+            .map(|(_, c)| *c)
+            .collect::<String>();
+
+        assert_eq!(diagonal, "ciga");
+        assert_eq!(adjacent, "abcfihgd");
+    }
+
+    #[test]
+    fn custom_stencils() {
+        let grid = Grid::from("abc\ndef\nghi");
+        let knight = [Point(1, 2), Point(2, 1), Point(-1, 2)];
+
+        let expected = vec![(Position::new(1, 2), &'h'), (Position::new(2, 1), &'f')];
+        let actual = grid.neighbours(Point(0, 0), &knight).collect::<Vec<_>>();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn wrapping_neighbours_come_back_on_the_other_side() {
+        let grid = Grid::from(EXAMPLE_INPUT);
+
+        let expected = "fdeb";
+        let actual = grid
+            .neighbours(Point(0, 0), &ADJACENT[..4])
+            .wrapping()
+            .map(|(_, c)| *c)
+            .collect::<String>();
+
+        assert_eq!(actual, expected);
+    }
+}
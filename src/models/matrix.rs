@@ -1,24 +1,35 @@
-use super::position::Position;
+use std::iter::FusedIterator;
 
-pub(crate) struct Matrix<T>(Vec<Vec<T>>);
+use super::{bounding_box::BoundingBox, point::Position};
+
+/// Rows of cells, which don't have to be the same length. The matrix is as
+/// wide as its longest row and missing cells read as `T::default()`.
+pub struct Matrix<T>(Vec<Vec<T>>);
 
 impl<T: Default + Clone> Matrix<T> {
-    pub(crate) fn n_columns(&self) -> usize {
-        self.0.first().map(|f| f.len()).unwrap()
+    pub fn n_columns(&self) -> usize {
+        self.0.iter().map(Vec::len).max().unwrap_or(0)
     }
 
-    pub(crate) fn n_rows(&self) -> usize {
+    pub fn n_rows(&self) -> usize {
         self.0.len()
     }
 
-    pub(crate) fn get_bounding_box(&self) -> BoundingBox {
-        BoundingBox {
-            top_left: Position::new(0, 0),
-            bottom_right: Position::new(self.n_columns() - 1, self.n_rows() - 1),
+    /// Box around every cell, or `None` when the matrix has no cells.
+    pub fn get_bounding_box(&self) -> Option<BoundingBox<usize>> {
+        let (n_columns, n_rows) = (self.n_columns(), self.n_rows());
+
+        if n_columns == 0 || n_rows == 0 {
+            return None;
         }
+
+        Some(BoundingBox::new(
+            Position::new(0, 0),
+            Position::new(n_columns - 1, n_rows - 1),
+        ))
     }
 
-    pub(crate) fn get(&self, x: usize, y: usize) -> T {
+    pub fn get(&self, x: usize, y: usize) -> T {
         self.0
             .get(y)
             .and_then(|line| line.get(x))
@@ -26,31 +37,28 @@ impl<T: Default + Clone> Matrix<T> {
             .unwrap_or_default()
     }
 
-    pub(crate) fn pairs(&self) -> MatrixCoordinates<T> {
+    pub fn pairs(&self) -> MatrixCoordinates<'_, T> {
         MatrixCoordinates {
             matrix: self,
-            row: 0,
-            col: 0,
+            cursor: Cursor::new(self),
         }
     }
 
-    pub(crate) fn iter(&self) -> MatrixIterator<T> {
+    pub fn iter(&self) -> MatrixIterator<'_, T> {
         MatrixIterator {
             matrix: self,
-            row: 0,
-            col: 0,
+            cursor: Cursor::new(self),
         }
     }
 
-    pub(crate) fn iter_with_pos(&self) -> MatrixIteratorWithPos<T> {
+    pub fn iter_with_pos(&self) -> MatrixIteratorWithPos<'_, T> {
         MatrixIteratorWithPos {
             matrix: self,
-            row: 0,
-            col: 0,
+            cursor: Cursor::new(self),
         }
     }
 
-    pub(crate) fn find_position_by(&self, pred: fn(T) -> bool) -> Option<Position> {
+    pub fn find_position_by(&self, pred: fn(T) -> bool) -> Option<Position> {
         for (x, y) in self.pairs() {
             let value = self.get(x, y);
 
@@ -69,104 +77,100 @@ impl<T: Default + Clone> From<Vec<Vec<T>>> for Matrix<T> {
     }
 }
 
-pub(crate) struct MatrixCoordinates<'a, T: Default + Clone> {
-    matrix: &'a Matrix<T>,
-    row: usize,
-    col: usize,
+/// The `(x, y)` coordinates still to visit, in row-major order, as a range of
+/// cell indices so both ends can be taken from.
+#[derive(Clone)]
+struct Cursor {
+    front: usize,
+    back: usize,
+    n_columns: usize,
 }
 
-impl<'a, T: Default + Clone> Iterator for MatrixCoordinates<'a, T> {
-    type Item = (usize, usize);
+impl Cursor {
+    fn new<T: Default + Clone>(matrix: &Matrix<T>) -> Self {
+        let n_columns = matrix.n_columns();
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.row >= self.matrix.n_rows() {
-            return None;
+        Self {
+            front: 0,
+            back: n_columns * matrix.n_rows(),
+            n_columns,
         }
+    }
 
-        let result = (self.col, self.row);
-
-        self.col += 1;
+    fn coordinates(&self, index: usize) -> (usize, usize) {
+        (index % self.n_columns, index / self.n_columns)
+    }
 
-        if self.col >= self.matrix.n_columns() {
-            self.col = 0;
-            self.row += 1;
+    fn next(&mut self) -> Option<(usize, usize)> {
+        if self.front >= self.back {
+            return None;
         }
 
-        Some(result)
-    }
-}
-
-pub(crate) struct MatrixIterator<'a, T: Default + Clone> {
-    matrix: &'a Matrix<T>,
-    row: usize,
-    col: usize,
-}
+        self.front += 1;
 
-impl<'a, T: Default + Clone> Iterator for MatrixIterator<'a, T> {
-    type Item = T;
+        Some(self.coordinates(self.front - 1))
+    }
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.row > self.matrix.n_rows() {
+    fn next_back(&mut self) -> Option<(usize, usize)> {
+        if self.front >= self.back {
             return None;
         }
 
-        let result = self.matrix.get(self.col, self.row);
+        self.back -= 1;
 
-        self.col += 1;
-
-        if self.col > self.matrix.n_columns() {
-            self.col = 0;
-            self.row += 1;
-        }
+        Some(self.coordinates(self.back))
+    }
 
-        Some(result)
+    fn len(&self) -> usize {
+        self.back - self.front
     }
 }
 
-pub(crate) struct MatrixIteratorWithPos<'a, T: Default + Clone> {
-    matrix: &'a Matrix<T>,
-    row: usize,
-    col: usize,
-}
+macro_rules! matrix_iterator {
+    ($name:ident, $item:ty, |$matrix:ident, $x:ident, $y:ident| $value:expr) => {
+        pub struct $name<'a, T: Default + Clone> {
+            matrix: &'a Matrix<T>,
+            cursor: Cursor,
+        }
 
-impl<'a, T: Default + Clone> Iterator for MatrixIteratorWithPos<'a, T> {
-    type Item = (T, Position);
+        impl<'a, T: Default + Clone> Iterator for $name<'a, T> {
+            type Item = $item;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.row > self.matrix.n_rows() {
-            return None;
-        }
+            fn next(&mut self) -> Option<Self::Item> {
+                let $matrix = self.matrix;
+                let ($x, $y) = self.cursor.next()?;
+
+                Some($value)
+            }
 
-        let result = (
-            self.matrix.get(self.col, self.row),
-            Position::new(self.col, self.row),
-        );
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.cursor.len(), Some(self.cursor.len()))
+            }
+        }
 
-        self.col += 1;
+        impl<'a, T: Default + Clone> DoubleEndedIterator for $name<'a, T> {
+            fn next_back(&mut self) -> Option<Self::Item> {
+                let $matrix = self.matrix;
+                let ($x, $y) = self.cursor.next_back()?;
 
-        if self.col >= self.matrix.n_columns() {
-            self.col = 0;
-            self.row += 1;
+                Some($value)
+            }
         }
 
-        Some(result)
-    }
-}
+        impl<'a, T: Default + Clone> ExactSizeIterator for $name<'a, T> {}
 
-#[derive(Default)]
-pub(crate) struct BoundingBox {
-    pub(crate) top_left: Position,
-    pub(crate) bottom_right: Position,
+        impl<'a, T: Default + Clone> FusedIterator for $name<'a, T> {}
+    };
 }
 
-impl BoundingBox {
-    pub fn contains(&self, position: &Position) -> bool {
-        position.x() >= self.top_left.x()
-            && position.y() >= self.top_left.y()
-            && position.x() <= self.bottom_right.x()
-            && position.y() <= self.bottom_right.y()
-    }
-}
+matrix_iterator!(MatrixCoordinates, (usize, usize), |_matrix, x, y| (x, y));
+
+matrix_iterator!(MatrixIterator, T, |matrix, x, y| matrix.get(x, y));
+
+matrix_iterator!(MatrixIteratorWithPos, (T, Position), |matrix, x, y| (
+    matrix.get(x, y),
+    Position::new(x, y)
+));
 
 impl From<&str> for Matrix<String> {
     fn from(value: &str) -> Self {
@@ -188,3 +192,116 @@ impl From<&str> for Matrix<String> {
         Self::from(nodes)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use crate::models::point::Position;
+
+    use super::Matrix;
+
+    #[test]
+    fn iterates_every_cell_once() {
+        let matrix = Matrix::from("ab\ncd");
+
+        let expected = vec!["a", "b", "c", "d"];
+        let actual = matrix.iter().collect::<Vec<String>>();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn empty_matrix() {
+        let matrix = Matrix::<u8>::from(vec![]);
+
+        assert_eq!(matrix.n_columns(), 0);
+        assert_eq!(matrix.iter().next(), None);
+        assert_eq!(matrix.pairs().len(), 0);
+        assert!(matrix.get_bounding_box().is_none());
+    }
+
+    #[test]
+    fn ragged_rows_are_padded_with_defaults() {
+        let matrix = Matrix::from(vec![vec![1], vec![2, 3, 4]]);
+
+        let expected = vec![
+            (1, Position::new(0, 0)),
+            (0, Position::new(1, 0)),
+            (0, Position::new(2, 0)),
+            (2, Position::new(0, 1)),
+            (3, Position::new(1, 1)),
+            (4, Position::new(2, 1)),
+        ];
+        let actual = matrix.iter_with_pos().collect::<Vec<_>>();
+
+        assert_eq!(actual, expected);
+    }
+
+    fn matrices() -> impl Strategy<Value = Vec<Vec<u8>>> {
+        prop::collection::vec(prop::collection::vec(any::<u8>(), 0..8), 0..8)
+    }
+
+    proptest! {
+        #[test]
+        fn count_is_rows_times_columns(rows in matrices()) {
+            let matrix = Matrix::from(rows);
+            let expected = matrix.n_rows() * matrix.n_columns();
+
+            prop_assert_eq!(matrix.iter().count(), expected);
+            prop_assert_eq!(matrix.iter().len(), expected);
+            prop_assert_eq!(matrix.iter_with_pos().count(), expected);
+            prop_assert_eq!(matrix.pairs().count(), expected);
+        }
+
+        #[test]
+        fn iterators_agree_with_pairs(rows in matrices()) {
+            let matrix = Matrix::from(rows);
+
+            let from_pairs = matrix
+                .pairs()
+                .map(|(x, y)| (matrix.get(x, y), Position::new(x, y)))
+                .collect::<Vec<_>>();
+
+            prop_assert_eq!(matrix.iter_with_pos().collect::<Vec<_>>(), from_pairs.clone());
+            prop_assert_eq!(
+                matrix.iter().collect::<Vec<_>>(),
+                from_pairs.into_iter().map(|(value, _)| value).collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn reversed_iteration_mirrors_forward(rows in matrices()) {
+            let matrix = Matrix::from(rows);
+
+            let mut forward = matrix.pairs().collect::<Vec<_>>();
+            forward.reverse();
+
+            prop_assert_eq!(matrix.pairs().rev().collect::<Vec<_>>(), forward);
+        }
+
+        #[test]
+        fn both_ends_meet_without_overlap(rows in matrices(), from_back in any::<Vec<bool>>()) {
+            let matrix = Matrix::from(rows);
+            let mut pairs = matrix.pairs();
+            let mut seen = Vec::new();
+
+            for back in from_back.into_iter().chain(std::iter::repeat(false)) {
+                let len = pairs.len();
+                let next = if back { pairs.next_back() } else { pairs.next() };
+
+                match next {
+                    Some(pair) => {
+                        prop_assert_eq!(pairs.len(), len - 1);
+                        seen.push(pair);
+                    }
+                    None => break,
+                }
+            }
+
+            seen.sort_by_key(|(x, y)| (*y, *x));
+
+            prop_assert_eq!(seen, matrix.pairs().collect::<Vec<_>>());
+        }
+    }
+}
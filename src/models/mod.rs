@@ -1,5 +1,10 @@
 pub mod bounding_box;
+pub mod dag;
+pub mod direction;
 pub mod grid;
-pub(crate) mod matrix;
+pub mod matrix;
 pub mod point;
-pub(crate) mod position;
+pub mod region;
+pub mod render;
+pub mod search;
+pub mod view;
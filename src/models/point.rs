@@ -1,136 +1,282 @@
 use std::{
     cmp::Ordering,
-    fmt::Display,
+    fmt::{Debug, Display},
+    hash::Hash,
     ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
 };
 
-#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
-pub struct Point(pub i32, pub i32);
+/// Integer types usable as coordinates. Offsets between coordinate types are
+/// done in `i128`, which holds every value of all of them.
+pub trait Coordinate:
+    Copy + Ord + Hash + Debug + Display + Default + Add<Output = Self> + Sub<Output = Self>
+{
+    fn to_i128(self) -> i128;
 
-impl Point {
-    pub fn x(&self) -> i32 {
+    fn from_i128(value: i128) -> Option<Self>;
+
+    fn saturating_from_i128(value: i128) -> Self;
+}
+
+macro_rules! coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+
+                fn from_i128(value: i128) -> Option<Self> {
+                    <$t>::try_from(value).ok()
+                }
+
+                fn saturating_from_i128(value: i128) -> Self {
+                    value.clamp(<$t>::MIN as i128, <$t>::MAX as i128) as $t
+                }
+            }
+        )*
+    };
+}
+
+coordinate!(i32, i64, i128, isize, u32, u64, usize);
+
+/// A point on a grid, with `x` growing east and `y` growing south.
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug, Default)]
+pub struct Point<T = i32>(pub T, pub T);
+
+/// A point which can only be on or right/below the origin, like an index
+/// into a grid.
+pub type Position = Point<usize>;
+
+impl<T: Copy> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self(x, y)
+    }
+
+    pub fn x(&self) -> T {
         self.0
     }
 
-    pub fn y(&self) -> i32 {
+    pub fn y(&self) -> T {
         self.1
     }
+}
 
-    pub fn x_usize(&self) -> usize {
-        self.0 as usize
+impl<T: Coordinate> Point<T> {
+    pub fn origin() -> Self {
+        Self::default()
     }
 
-    pub fn y_usize(&self) -> usize {
-        self.1 as usize
+    /// Component-wise absolute difference.
+    pub fn diff(&self, rhs: &Self) -> Self {
+        Self(
+            self.0.max(rhs.0) - self.0.min(rhs.0),
+            self.1.max(rhs.1) - self.1.min(rhs.1),
+        )
     }
 
-    pub fn diff(&self, rhs: &Point) -> Point {
-        Point::from((self.0.abs_diff(rhs.0), self.1.abs_diff(rhs.1)))
+    pub fn distance_to(&self, other: &Self) -> Distance {
+        Distance::new(
+            self.0.to_i128().abs_diff(other.0.to_i128()) as usize,
+            self.1.to_i128().abs_diff(other.1.to_i128()) as usize,
+        )
     }
 
-    pub fn origin() -> Point {
-        Point(0, 0)
+    /// Moves the point by `offset`, or `None` when the result doesn't fit in
+    /// `T`, e.g. when stepping west of column 0 of a [`Position`].
+    pub fn checked_offset<D: Coordinate>(&self, offset: Point<D>) -> Option<Self> {
+        Some(Self(
+            T::from_i128(self.0.to_i128() + offset.0.to_i128())?,
+            T::from_i128(self.1.to_i128() + offset.1.to_i128())?,
+        ))
     }
-}
 
-impl From<(usize, usize)> for Point {
-    fn from(value: (usize, usize)) -> Self {
-        Self(value.0 as i32, value.1 as i32)
+    /// Moves the point by `offset`, clamping each coordinate to what `T` can
+    /// hold.
+    pub fn saturating_offset<D: Coordinate>(&self, offset: Point<D>) -> Self {
+        Self(
+            T::saturating_from_i128(self.0.to_i128() + offset.0.to_i128()),
+            T::saturating_from_i128(self.1.to_i128() + offset.1.to_i128()),
+        )
+    }
+
+    /// Converts to another coordinate type, or `None` if either coordinate is
+    /// out of its range.
+    pub fn cast<U: Coordinate>(&self) -> Option<Point<U>> {
+        Point::origin().checked_offset(*self)
+    }
+
+    pub fn compare_y(&self, other: &Self) -> Ordering {
+        self.1.cmp(&other.1)
+    }
+
+    pub fn compare_x(&self, other: &Self) -> Ordering {
+        self.0.cmp(&other.0)
     }
-}
 
-impl From<(u32, u32)> for Point {
-    fn from(value: (u32, u32)) -> Self {
-        Self(value.0 as i32, value.1 as i32)
+    pub fn north_of(&self, from: &Self) -> bool {
+        self.1 < from.1 && self.0 == from.0
+    }
+
+    pub fn south_of(&self, from: &Self) -> bool {
+        self.1 > from.1 && self.0 == from.0
+    }
+
+    pub fn east_of(&self, from: &Self) -> bool {
+        self.0 > from.0 && self.1 == from.1
+    }
+
+    pub fn west_of(&self, from: &Self) -> bool {
+        self.0 < from.0 && self.1 == from.1
     }
 }
 
-impl From<(i32, i32)> for Point {
-    fn from(value: (i32, i32)) -> Self {
+impl<T> From<(T, T)> for Point<T> {
+    fn from(value: (T, T)) -> Self {
         Self(value.0, value.1)
     }
 }
 
-impl Add for Point {
-    type Output = Point;
+impl<T> From<Point<T>> for (T, T) {
+    fn from(value: Point<T>) -> Self {
+        (value.0, value.1)
+    }
+}
+
+// A blanket impl over `U: From<T>` would overlap with `From<T> for T`, so the
+// conversions are spelled out for each pair of coordinate types.
+macro_rules! lossless {
+    ($($from:ty => $($to:ty),*);*) => {
+        $($(
+            impl From<Point<$from>> for Point<$to> {
+                fn from(value: Point<$from>) -> Self {
+                    Self(<$to>::from(value.0), <$to>::from(value.1))
+                }
+            }
+        )*)*
+    };
+}
+
+macro_rules! fallible {
+    ($($from:ty => $($to:ty),*);*) => {
+        $($(
+            impl TryFrom<Point<$from>> for Point<$to> {
+                type Error = std::num::TryFromIntError;
+
+                fn try_from(value: Point<$from>) -> Result<Self, Self::Error> {
+                    Ok(Self(<$to>::try_from(value.0)?, <$to>::try_from(value.1)?))
+                }
+            }
+        )*)*
+    };
+}
+
+lossless! {
+    i32 => i64;
+    u32 => i64, u64
+}
+
+fallible! {
+    i32 => isize, u32, u64, usize;
+    i64 => i32, isize, u32, u64, usize;
+    isize => i32, i64, u32, u64, usize;
+    u32 => i32, isize, usize;
+    u64 => i32, i64, isize, u32, usize;
+    usize => i32, i64, isize, u32, u64
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Point<T>;
 
     fn add(self, rhs: Self) -> Self::Output {
         Self(self.0 + rhs.0, self.1 + rhs.1)
     }
 }
 
-impl AddAssign for Point {
+impl<T: AddAssign> AddAssign for Point<T> {
     fn add_assign(&mut self, rhs: Self) {
         self.0 += rhs.0;
         self.1 += rhs.1;
     }
 }
 
-impl Sub for Point {
-    type Output = Point;
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Point<T>;
 
     fn sub(self, rhs: Self) -> Self::Output {
         Self(self.0 - rhs.0, self.1 - rhs.1)
     }
 }
 
-impl SubAssign for Point {
+impl<T: SubAssign> SubAssign for Point<T> {
     fn sub_assign(&mut self, rhs: Self) {
         self.0 -= rhs.0;
         self.1 -= rhs.1;
     }
 }
 
-impl Mul<i32> for Point {
-    type Output = Point;
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Point<T>;
 
-    fn mul(self, rhs: i32) -> Self::Output {
+    fn mul(self, rhs: T) -> Self::Output {
         Self(self.0 * rhs, self.1 * rhs)
     }
 }
 
-impl Neg for Point {
-    type Output = Point;
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Point<T>;
 
     fn neg(self) -> Self::Output {
         Self(-self.0, -self.1)
     }
 }
 
-impl Ord for Point {
+/// Points are ordered row by row, by `y` and then by `x`.
+impl<T: Ord> Ord for Point<T> {
     fn cmp(&self, other: &Self) -> Ordering {
-        let lhs = self.0.cmp(&other.0);
-        let rhs = self.1.cmp(&other.1);
-
-        match (lhs, rhs) {
-            (Ordering::Equal, Ordering::Equal) => Ordering::Equal,
-            (Ordering::Equal, Ordering::Greater) => Ordering::Greater,
-            (Ordering::Equal, Ordering::Less) => Ordering::Less,
-            (Ordering::Greater, Ordering::Equal) => Ordering::Greater,
-            (Ordering::Greater, Ordering::Greater) => Ordering::Greater,
-            (Ordering::Greater, Ordering::Less) => Ordering::Less,
-            (Ordering::Less, Ordering::Equal) => Ordering::Less,
-            (Ordering::Less, Ordering::Less) => Ordering::Less,
-            (Ordering::Less, Ordering::Greater) => Ordering::Less,
-        }
+        (&self.1, &self.0).cmp(&(&other.1, &other.0))
     }
 }
 
-impl PartialOrd for Point {
+impl<T: Ord> PartialOrd for Point<T> {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         Some(self.cmp(other))
     }
 }
 
-impl Display for Point {
+impl<T: Display> Display for Point<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "({},{})", self.0, self.1)
     }
 }
 
+/// How far apart two points are along each axis.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Distance {
+    x: usize,
+    y: usize,
+}
+
+impl Distance {
+    pub fn new(x: usize, y: usize) -> Self {
+        Distance { x, y }
+    }
+
+    pub fn x(&self) -> usize {
+        self.x
+    }
+
+    pub fn y(&self) -> usize {
+        self.y
+    }
+
+    pub fn manhattan(&self) -> usize {
+        self.x + self.y
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::models::point::Point;
+    use crate::models::point::{Distance, Point, Position};
 
     #[test]
     fn subtraction_should_work() {
@@ -179,14 +325,14 @@ mod tests {
         let a = Point::from((0, 0));
         let b = Point::from((1, 1));
 
-        assert!(b < a)
+        assert!(a < b)
     }
 
     #[test]
     fn comparison_works() {
         let origin = Point::origin();
 
-        assert!(Point::from((-1, 1)) < origin);
+        assert!(Point::from((-1, 1)) > origin);
         assert!(Point::from((-1, 0)) < origin);
         assert!(Point::from((-1, -1)) < origin);
         assert!(Point::from((0, -1)) < origin);
@@ -195,4 +341,66 @@ mod tests {
         assert!(Point::from((1, 1)) > origin);
         assert!(Point::from((0, 1)) > origin);
     }
+
+    #[test]
+    fn test_north_of() {
+        assert!(Position::new(2, 0).north_of(&Position::new(2, 2)))
+    }
+
+    #[test]
+    fn test_east_of() {
+        assert!(Position::new(4, 2).east_of(&Position::new(2, 2)))
+    }
+
+    #[test]
+    fn test_south_of() {
+        assert!(Position::new(2, 4).south_of(&Position::new(2, 2)))
+    }
+
+    #[test]
+    fn test_west_of() {
+        assert!(Position::new(0, 2).west_of(&Position::new(2, 2)))
+    }
+
+    #[test]
+    fn checked_offset_stops_at_the_edge() {
+        let corner = Position::new(0, 3);
+
+        assert_eq!(
+            corner.checked_offset(Point(0, -1)),
+            Some(Position::new(0, 2))
+        );
+        assert_eq!(corner.checked_offset(Point(-1, 0)), None);
+    }
+
+    #[test]
+    fn saturating_offset_clamps() {
+        let corner = Position::new(1, 3);
+
+        assert_eq!(corner.saturating_offset(Point(-5, 2)), Position::new(0, 5));
+        assert_eq!(
+            Point(i32::MAX, 0).saturating_offset(Point(1_i64, 0)),
+            Point(i32::MAX, 0)
+        );
+    }
+
+    #[test]
+    fn conversions_between_signed_and_unsigned() {
+        let point: Point<i64> = Point(3_u32, 4_u32).into();
+        assert_eq!(point, Point(3_i64, 4_i64));
+
+        assert_eq!(Position::try_from(Point(3, 4)), Ok(Position::new(3, 4)));
+        assert!(Position::try_from(Point(-1, 4)).is_err());
+        assert_eq!(Position::new(2, 5).cast::<i32>(), Some(Point(2, 5)));
+        assert_eq!(Point(-2, 5).cast::<usize>(), None);
+    }
+
+    #[test]
+    fn distance_to_works_across_the_origin() {
+        let a = Point(-2, 3);
+        let b = Point(1, -1);
+
+        assert_eq!(a.distance_to(&b), Distance::new(3, 4));
+        assert_eq!(a.distance_to(&b).manhattan(), 7);
+    }
 }
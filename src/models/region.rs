@@ -0,0 +1,237 @@
+use std::collections::HashSet;
+
+use super::{
+    direction::Direction,
+    grid::{Grid, ADJACENT, ORTHOGONAL},
+    point::{Point, Position},
+    search::bfs,
+};
+
+/// Which cells count as touching when growing a region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// Cells sharing an edge.
+    Four,
+    /// Cells sharing an edge or a corner.
+    Eight,
+}
+
+impl Connectivity {
+    pub fn offsets(self) -> &'static [Point] {
+        match self {
+            Connectivity::Four => &ORTHOGONAL,
+            Connectivity::Eight => &ADJACENT,
+        }
+    }
+}
+
+/// A set of connected cells of a grid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    cells: HashSet<Position>,
+}
+
+impl Region {
+    pub fn cells(&self) -> &HashSet<Position> {
+        &self.cells
+    }
+
+    pub fn contains(&self, position: Position) -> bool {
+        self.cells.contains(&position)
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Number of cell edges on the outline of the region, holes included.
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|cell| {
+                Direction::CARDINAL
+                    .into_iter()
+                    .filter(|direction| !self.has_neighbour(*cell, *direction))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Number of straight sides of the outline, holes included. A straight
+    /// side runs between two corners, so this is the same as [`Region::corners`].
+    pub fn sides(&self) -> usize {
+        self.corners()
+    }
+
+    /// Number of corners of the outline, holes included.
+    pub fn corners(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|cell| {
+                // Look at the four corners of each cell, between a pair of
+                // neighbouring cardinal directions.
+                Direction::CARDINAL
+                    .into_iter()
+                    .filter(|direction| {
+                        let side = self.has_neighbour(*cell, *direction);
+                        let other_side = self.has_neighbour(*cell, direction.turn_right());
+                        let diagonal = self.has_neighbour(*cell, direction.rotate(1));
+
+                        // Either the outline turns around this cell, or it
+                        // turns around the missing diagonal cell.
+                        (!side && !other_side) || (side && other_side && !diagonal)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    fn has_neighbour(&self, cell: Position, direction: Direction) -> bool {
+        cell.checked_offset(direction.offset())
+            .is_some_and(|neighbour| self.cells.contains(&neighbour))
+    }
+}
+
+/// Every region of a grid, see [`Grid::regions`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Regions {
+    /// The index into `regions` of the region each cell belongs to.
+    pub labels: Grid<usize>,
+    pub regions: Vec<Region>,
+}
+
+impl<T> Grid<T> {
+    /// The region containing `seed`, grown through neighbouring cells for
+    /// which `same(cell, neighbour)` holds.
+    ///
+    /// `same` has to be an equivalence relation, otherwise the region depends
+    /// on where the fill starts.
+    ///
+    /// Panics when `seed` is outside of the grid.
+    pub fn flood_fill(
+        &self,
+        seed: Position,
+        connectivity: Connectivity,
+        mut same: impl FnMut(&T, &T) -> bool,
+    ) -> Region {
+        assert!(self.contains(seed), "{} is outside of the grid", seed);
+
+        let cells = bfs(seed, |cell| {
+            let value = &self[*cell];
+
+            self.neighbours(*cell, connectivity.offsets())
+                .filter(|(_, neighbour)| same(value, neighbour))
+                .map(|(position, _)| position)
+                .collect::<Vec<Position>>()
+        })
+        .map(|(cell, _)| cell)
+        .collect();
+
+        Region { cells }
+    }
+
+    /// Splits the whole grid into regions, in the order of their first cell in
+    /// row-major order.
+    ///
+    /// As with [`Grid::flood_fill`], `same` has to be an equivalence relation
+    /// for every cell to end up in exactly one region.
+    pub fn regions(
+        &self,
+        connectivity: Connectivity,
+        mut same: impl FnMut(&T, &T) -> bool,
+    ) -> Regions {
+        let mut labels = Grid::new(self.width(), self.height(), usize::MAX);
+        let mut regions = Vec::new();
+
+        for position in self.positions() {
+            if labels[position] != usize::MAX {
+                continue;
+            }
+
+            let region = self.flood_fill(position, connectivity, &mut same);
+
+            for cell in region.cells() {
+                labels[*cell] = regions.len();
+            }
+
+            regions.push(region);
+        }
+
+        Regions { labels, regions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{grid::Grid, point::Position};
+
+    use super::{Connectivity, Region};
+
+    const EXAMPLE_INPUT: &str = "AAAA\nBBCD\nBBCC\nEEEC";
+    const HOLES: &str = "OOOOO\nOXOXO\nOOOOO\nOXOXO\nOOOOO";
+
+    fn regions(input: &str) -> Vec<Region> {
+        Grid::from(input)
+            .regions(Connectivity::Four, |a, b| a == b)
+            .regions
+    }
+
+    #[test]
+    fn area_perimeter_and_sides() {
+        let expected = vec![(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)];
+        let actual = regions(EXAMPLE_INPUT)
+            .iter()
+            .map(|region| (region.area(), region.perimeter(), region.sides()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn holes_count_towards_perimeter_and_sides() {
+        let regions = regions(HOLES);
+
+        assert_eq!(regions.len(), 5);
+        assert_eq!(regions[0].area(), 21);
+        assert_eq!(regions[0].perimeter(), 36);
+        assert_eq!(regions[0].sides(), 20);
+    }
+
+    #[test]
+    fn flood_fill_from_a_seed() {
+        let grid = Grid::from(EXAMPLE_INPUT);
+
+        let region = grid.flood_fill(Position::new(2, 1), Connectivity::Four, |a, b| a == b);
+
+        assert_eq!(region.area(), 4);
+        assert!(region.contains(Position::new(3, 3)));
+        assert!(!region.contains(Position::new(3, 1)));
+    }
+
+    #[test]
+    fn eight_connectivity_joins_diagonals() {
+        let grid = Grid::from("X.\n.X");
+
+        let four = grid.regions(Connectivity::Four, |a, b| a == b);
+        let eight = grid.regions(Connectivity::Eight, |a, b| a == b);
+
+        assert_eq!(four.regions.len(), 4);
+        assert_eq!(eight.regions.len(), 2);
+        assert_eq!(
+            eight.labels[Position::new(0, 0)],
+            eight.labels[Position::new(1, 1)]
+        );
+    }
+
+    #[test]
+    fn custom_equivalence() {
+        let grid = Grid::parse("1239\n8765", |c| c.to_digit(10).unwrap());
+
+        // Cells join when their values are both odd or both even.
+        let regions = grid.regions(Connectivity::Four, |a, b| a % 2 == b % 2);
+
+        assert_eq!(regions.regions.len(), 6);
+        assert_eq!(regions.regions[2].area(), 3);
+        assert_eq!(regions.labels[Position::new(3, 1)], 2);
+    }
+}
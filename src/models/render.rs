@@ -0,0 +1,226 @@
+//! Drawing grids as text or as images, to look at what a solver did.
+
+use std::{
+    collections::HashMap,
+    fmt::{Display, Write as _},
+};
+
+use super::{
+    grid::Grid,
+    point::{Coordinate, Point, Position},
+};
+
+/// A 24-bit RGB colour.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Colour(pub u8, pub u8, pub u8);
+
+impl Colour {
+    pub const BLACK: Colour = Colour(0, 0, 0);
+    pub const WHITE: Colour = Colour(255, 255, 255);
+    pub const RED: Colour = Colour(220, 50, 47);
+    pub const GREEN: Colour = Colour(133, 153, 0);
+    pub const BLUE: Colour = Colour(38, 139, 210);
+    pub const YELLOW: Colour = Colour(181, 137, 0);
+}
+
+/// How the cells of an overlay are drawn. Without a glyph the cell keeps its
+/// own character, without a colour it keeps the terminal's.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Style {
+    pub glyph: Option<char>,
+    pub colour: Option<Colour>,
+}
+
+impl Style {
+    pub fn glyph(glyph: char) -> Style {
+        Style {
+            glyph: Some(glyph),
+            colour: None,
+        }
+    }
+
+    pub fn colour(colour: Colour) -> Style {
+        Style {
+            glyph: None,
+            colour: Some(colour),
+        }
+    }
+
+    pub fn with_colour(self, colour: Colour) -> Style {
+        Style {
+            colour: Some(colour),
+            ..self
+        }
+    }
+}
+
+/// A grid being drawn as text, see [`Grid::render`].
+pub struct Render<'a, T, F> {
+    grid: &'a Grid<T>,
+    cell: F,
+    styles: HashMap<Position, Style>,
+}
+
+impl<T> Grid<T> {
+    /// Draws the grid one character per cell, using `cell` for the character.
+    /// Layers are added on top with [`Render::overlay`].
+    pub fn render<F: Fn(&T) -> char>(&self, cell: F) -> Render<'_, T, F> {
+        Render {
+            grid: self,
+            cell,
+            styles: HashMap::new(),
+        }
+    }
+
+    /// Encodes the grid as a binary PPM image, with each cell drawn as a
+    /// `scale`×`scale` square of the colour returned by `colour`.
+    pub fn to_ppm(&self, scale: usize, colour: impl Fn(Position, &T) -> Colour) -> Vec<u8> {
+        let width = self.width() * scale;
+        let height = self.height() * scale;
+        let mut image = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+
+        image.reserve(width * height * 3);
+
+        for (y, row) in self.rows().enumerate() {
+            let pixels = row
+                .iter()
+                .enumerate()
+                .flat_map(|(x, cell)| {
+                    let Colour(r, g, b) = colour(Position::new(x, y), cell);
+
+                    [r, g, b].repeat(scale)
+                })
+                .collect::<Vec<u8>>();
+
+            for _ in 0..scale {
+                image.extend_from_slice(&pixels);
+            }
+        }
+
+        image
+    }
+}
+
+impl<T, F: Fn(&T) -> char> Render<'_, T, F> {
+    /// Draws `points` with `style`, over the grid and any earlier overlay.
+    /// Points outside of the grid are ignored.
+    pub fn overlay<C: Coordinate>(
+        mut self,
+        points: impl IntoIterator<Item = Point<C>>,
+        style: Style,
+    ) -> Self {
+        for point in points {
+            let Some(position) = point.cast::<usize>() else {
+                continue;
+            };
+
+            if self.grid.contains(position) {
+                let current = self.styles.entry(position).or_default();
+
+                *current = Style {
+                    glyph: style.glyph.or(current.glyph),
+                    colour: style.colour.or(current.colour),
+                };
+            }
+        }
+
+        self
+    }
+}
+
+impl<T, F: Fn(&T) -> char> Display for Render<'_, T, F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.grid.rows().enumerate() {
+            let mut line = String::with_capacity(row.len());
+
+            for (x, cell) in row.iter().enumerate() {
+                let style = self
+                    .styles
+                    .get(&Position::new(x, y))
+                    .copied()
+                    .unwrap_or_default();
+                let glyph = style.glyph.unwrap_or_else(|| (self.cell)(cell));
+
+                match style.colour {
+                    Some(Colour(r, g, b)) => {
+                        let _ = write!(line, "\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, glyph);
+                    }
+                    None => line.push(glyph),
+                }
+            }
+
+            writeln!(f, "{}", line)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Grid<char> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(|c| *c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        grid::Grid,
+        point::{Point, Position},
+    };
+
+    use super::{Colour, Style};
+
+    const EXAMPLE_INPUT: &str = "...\n.#.\n...";
+
+    #[test]
+    fn plain_text() {
+        let grid = Grid::from(EXAMPLE_INPUT);
+
+        assert_eq!(grid.to_string(), "...\n.#.\n...\n");
+    }
+
+    #[test]
+    fn glyph_overlays_stack_in_order() {
+        let grid = Grid::from(EXAMPLE_INPUT);
+
+        let expected = "XX.\n.#.\n..O\n";
+        let actual = grid
+            .render(|c| *c)
+            .overlay([Point(0, 0), Point(1, 0), Point(2, 2)], Style::glyph('X'))
+            .overlay([Position::new(2, 2)], Style::glyph('O'))
+            .overlay([Point(-1, 0), Point(3, 3)], Style::glyph('!'))
+            .to_string();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn colour_overlays_keep_the_glyph() {
+        let grid = Grid::from(EXAMPLE_INPUT);
+
+        let expected = "...\n.\x1b[38;2;220;50;47m#\x1b[0m.\n...\n";
+        let actual = grid
+            .render(|c| *c)
+            .overlay([Point(1, 1)], Style::colour(Colour::RED))
+            .to_string();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn ppm_image() {
+        let grid = Grid::from("#.");
+
+        let image = grid.to_ppm(2, |_, c| match c {
+            '#' => Colour::BLACK,
+            _ => Colour::WHITE,
+        });
+
+        let header = b"P6\n4 2\n255\n";
+        let row = [0, 0, 0, 0, 0, 0, 255, 255, 255, 255, 255, 255];
+
+        assert_eq!(&image[..header.len()], header);
+        assert_eq!(&image[header.len()..], [row, row].concat());
+    }
+}
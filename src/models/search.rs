@@ -0,0 +1,324 @@
+//! Graph searches over implicit graphs.
+//!
+//! Nothing here knows about grids, the graph is described by a closure
+//! returning the neighbours of a node, so the same searches work on grid
+//! positions, `(Position, Direction)` states or anything else hashable.
+
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Breadth first traversal yielding each reachable node once, along with the
+/// number of steps it takes to reach it. Nodes come out in order of distance.
+pub fn bfs<N, F, I>(start: N, neighbours: F) -> Bfs<N, F>
+where
+    N: Eq + Hash + Clone,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    Bfs {
+        visited: HashSet::from([start.clone()]),
+        queue: VecDeque::from([(start, 0)]),
+        neighbours,
+    }
+}
+
+pub struct Bfs<N, F> {
+    queue: VecDeque<(N, usize)>,
+    visited: HashSet<N>,
+    neighbours: F,
+}
+
+impl<N, F, I> Iterator for Bfs<N, F>
+where
+    N: Eq + Hash + Clone,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    type Item = (N, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (node, depth) = self.queue.pop_front()?;
+
+        for next in (self.neighbours)(&node) {
+            if self.visited.insert(next.clone()) {
+                self.queue.push_back((next, depth + 1));
+            }
+        }
+
+        Some((node, depth))
+    }
+}
+
+/// Depth first traversal yielding each reachable node once, in pre-order.
+pub fn dfs<N, F, I>(start: N, neighbours: F) -> Dfs<N, F>
+where
+    N: Eq + Hash + Clone,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    Dfs {
+        stack: vec![start],
+        visited: HashSet::new(),
+        neighbours,
+    }
+}
+
+pub struct Dfs<N, F> {
+    stack: Vec<N>,
+    visited: HashSet<N>,
+    neighbours: F,
+}
+
+impl<N, F, I> Iterator for Dfs<N, F>
+where
+    N: Eq + Hash + Clone,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+{
+    type Item = N;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let node = self.stack.pop()?;
+
+            if !self.visited.insert(node.clone()) {
+                continue;
+            }
+
+            // Pushed in reverse so the first neighbour is explored first.
+            let mut next = (self.neighbours)(&node)
+                .into_iter()
+                .filter(|next| !self.visited.contains(next))
+                .collect::<Vec<N>>();
+            next.reverse();
+
+            self.stack.extend(next);
+
+            return Some(node);
+        }
+    }
+}
+
+/// A path found by [`dijkstra`] or [`astar`], from the start to the goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+/// Cheapest path from `start` to the first node matching `is_goal`.
+/// `neighbours` returns each neighbour with the cost of the step to it, which
+/// must not be negative.
+pub fn dijkstra<N, C, F, I>(
+    start: N,
+    neighbours: F,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbours, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but explores nodes in order of cost so far plus
+/// `heuristic`. The path is the cheapest one as long as the heuristic never
+/// overestimates the remaining cost, e.g. the manhattan distance on a grid.
+pub fn astar<N, C, F, I>(
+    start: N,
+    mut neighbours: F,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Eq + Hash + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    F: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // Nodes live in `nodes` and are referred to by index everywhere else, so
+    // they don't have to be `Ord` to go in the heap.
+    let mut nodes = vec![start.clone()];
+    let mut indices = HashMap::from([(start.clone(), 0)]);
+    let mut best: Vec<(C, Option<usize>)> = vec![(C::default(), None)];
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        // A cheaper way to this node was found after this entry was pushed.
+        if cost > best[i].0 {
+            continue;
+        }
+
+        let node = nodes[i].clone();
+
+        if is_goal(&node) {
+            let mut path = vec![node];
+            let mut parent = best[i].1;
+
+            while let Some(p) = parent {
+                path.push(nodes[p].clone());
+                parent = best[p].1;
+            }
+
+            path.reverse();
+
+            return Some(Path { nodes: path, cost });
+        }
+
+        for (next, step) in neighbours(&node) {
+            let next_cost = cost + step;
+
+            let j = match indices.get(&next) {
+                Some(&j) if next_cost >= best[j].0 => continue,
+                Some(&j) => {
+                    best[j] = (next_cost, Some(i));
+                    j
+                }
+                None => {
+                    let j = nodes.len();
+                    indices.insert(next.clone(), j);
+                    nodes.push(next.clone());
+                    best.push((next_cost, Some(i)));
+                    j
+                }
+            };
+
+            heap.push(Reverse((next_cost + heuristic(&next), next_cost, j)));
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::models::{direction::Direction, grid::Grid, point::Position};
+
+    use super::{astar, bfs, dfs, dijkstra};
+
+    const MAZE: &str = "..#.\n.##.\n....";
+
+    fn open_neighbours(grid: &Grid<char>, from: &Position) -> Vec<Position> {
+        grid.orthogonal_neighbours(*from)
+            .filter(|(_, c)| **c == '.')
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    #[test]
+    fn bfs_yields_nodes_by_distance() {
+        let grid = Grid::from(MAZE);
+
+        let distances = bfs(Position::new(0, 0), |p| open_neighbours(&grid, p))
+            .collect::<HashMap<Position, usize>>();
+
+        assert_eq!(distances.len(), 9);
+        assert_eq!(distances[&Position::new(3, 0)], 7);
+        assert!(!distances.contains_key(&Position::new(2, 0)));
+    }
+
+    #[test]
+    fn dfs_goes_deep_first() {
+        let edges = HashMap::from([(1, vec![2, 3]), (2, vec![4]), (3, vec![4]), (4, vec![])]);
+
+        let expected = vec![1, 2, 4, 3];
+        let actual = dfs(1, |n| edges[n].clone()).collect::<Vec<i32>>();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn dijkstra_finds_the_cheapest_path() {
+        let edges = HashMap::from([
+            ('a', vec![('b', 1), ('c', 5)]),
+            ('b', vec![('c', 1), ('d', 7)]),
+            ('c', vec![('d', 2)]),
+            ('d', vec![]),
+        ]);
+
+        let path = dijkstra('a', |n| edges[n].clone(), |n| *n == 'd').unwrap();
+
+        assert_eq!(path.nodes, vec!['a', 'b', 'c', 'd']);
+        assert_eq!(path.cost, 4);
+    }
+
+    #[test]
+    fn astar_on_a_grid() {
+        let grid = Grid::from(MAZE);
+        let goal = Position::new(3, 0);
+
+        let path = astar(
+            Position::new(0, 0),
+            |p| {
+                open_neighbours(&grid, p)
+                    .into_iter()
+                    .map(|next| (next, 1))
+                    .collect::<Vec<_>>()
+            },
+            |p| p.distance_to(&goal).manhattan(),
+            |p| *p == goal,
+        )
+        .unwrap();
+
+        assert_eq!(path.cost, 7);
+        assert_eq!(path.nodes.len(), 8);
+        assert_eq!(path.nodes.first(), Some(&Position::new(0, 0)));
+        assert_eq!(path.nodes.last(), Some(&goal));
+    }
+
+    #[test]
+    fn search_over_states() {
+        // Moving forward costs 1, turning costs 1000, like a maze where you
+        // have to face the way you walk.
+        let grid = Grid::from(MAZE);
+        let goal = Position::new(3, 2);
+
+        let path = dijkstra(
+            (Position::new(0, 0), Direction::East),
+            |(position, direction): &(Position, Direction)| {
+                let mut next = vec![
+                    ((*position, direction.turn_left()), 1000),
+                    ((*position, direction.turn_right()), 1000),
+                ];
+
+                if let Some(forward) = position.checked_offset(direction.offset()) {
+                    if grid.get(forward) == Some(&'.') {
+                        next.push(((forward, *direction), 1));
+                    }
+                }
+
+                next
+            },
+            |(position, _)| *position == goal,
+        )
+        .unwrap();
+
+        assert_eq!(path.cost, 2005);
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let grid = Grid::from(MAZE);
+
+        let path = dijkstra(
+            Position::new(0, 0),
+            |p| {
+                open_neighbours(&grid, p)
+                    .into_iter()
+                    .map(|next| (next, 1))
+                    .collect::<Vec<_>>()
+            },
+            |p| *p == Position::new(2, 0),
+        );
+
+        assert_eq!(path, None);
+    }
+}
@@ -0,0 +1,286 @@
+use super::{
+    grid::Grid,
+    point::{Coordinate, Point, Position},
+};
+
+/// A borrowed, possibly transformed, rectangle of a [`Grid`].
+///
+/// Nothing is copied: a view only records where its origin is in the grid and
+/// which way its `x` and `y` axes point, so transforms can be chained freely
+/// and cost the same as a plain lookup.
+#[derive(Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    /// Position in the grid of the view's `(0, 0)`.
+    origin: Point<isize>,
+    /// Step in the grid for one step along the view's `x` axis.
+    dx: Point<isize>,
+    /// Step in the grid for one step along the view's `y` axis.
+    dy: Point<isize>,
+    width: usize,
+    height: usize,
+}
+
+// Derived impls would require `T: Clone`.
+impl<T> Clone for GridView<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GridView<'_, T> {}
+
+impl<T> Grid<T> {
+    /// A view of the whole grid.
+    pub fn view(&self) -> GridView<'_, T> {
+        GridView {
+            grid: self,
+            origin: Point(0, 0),
+            dx: Point(1, 0),
+            dy: Point(0, 1),
+            width: self.width(),
+            height: self.height(),
+        }
+    }
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.width == 0 || self.height == 0
+    }
+
+    /// Where `point` of the view is in the underlying grid.
+    pub fn source<C: Coordinate>(&self, point: Point<C>) -> Option<Position> {
+        let Point(x, y) = point.cast::<usize>()?;
+
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        let Point(x, y) = self.origin + self.dx * x as isize + self.dy * y as isize;
+
+        Some(Position::new(x as usize, y as usize))
+    }
+
+    pub fn get<C: Coordinate>(&self, point: Point<C>) -> Option<&'a T> {
+        let grid = self.grid;
+
+        self.source(point).map(|position| &grid[position])
+    }
+
+    /// Every position in the view, in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Position::new(x, y)))
+    }
+
+    /// Cells in the view's row-major order, with their position in the view.
+    pub fn cells(self) -> impl Iterator<Item = (Position, &'a T)> {
+        self.positions()
+            .map(move |position| (position, &self.grid[self.source(position).unwrap()]))
+    }
+
+    pub fn row(self, y: usize) -> Option<impl Iterator<Item = &'a T>> {
+        (y < self.height).then(move || {
+            (0..self.width).map(move |x| &self.grid[self.source(Position::new(x, y)).unwrap()])
+        })
+    }
+
+    pub fn column(self, x: usize) -> Option<impl Iterator<Item = &'a T>> {
+        (x < self.width).then(move || {
+            (0..self.height).map(move |y| &self.grid[self.source(Position::new(x, y)).unwrap()])
+        })
+    }
+
+    /// Swaps rows and columns, mirroring along the main diagonal.
+    pub fn transpose(self) -> Self {
+        Self {
+            dx: self.dy,
+            dy: self.dx,
+            width: self.height,
+            height: self.width,
+            ..self
+        }
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(self) -> Self {
+        Self {
+            origin: self.origin + self.dx * self.last_x(),
+            dx: -self.dx,
+            ..self
+        }
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(self) -> Self {
+        Self {
+            origin: self.origin + self.dy * self.last_y(),
+            dy: -self.dy,
+            ..self
+        }
+    }
+
+    /// Rotates a quarter turn clockwise.
+    pub fn rotate_90(self) -> Self {
+        self.transpose().flip_horizontal()
+    }
+
+    pub fn rotate_180(self) -> Self {
+        self.flip_horizontal().flip_vertical()
+    }
+
+    /// Rotates a quarter turn counter-clockwise.
+    pub fn rotate_270(self) -> Self {
+        self.transpose().flip_vertical()
+    }
+
+    /// The part of the view starting at `top_left`, clipped to the view.
+    pub fn crop(self, top_left: Position, width: usize, height: usize) -> Self {
+        let x = top_left.x().min(self.width);
+        let y = top_left.y().min(self.height);
+
+        Self {
+            origin: self.origin + self.dx * x as isize + self.dy * y as isize,
+            width: width.min(self.width - x),
+            height: height.min(self.height - y),
+            ..self
+        }
+    }
+
+    /// Every `size`×`size` square in the view, with its top left position, in
+    /// row-major order.
+    ///
+    /// Panics when `size` is 0.
+    pub fn windows(self, size: usize) -> impl Iterator<Item = (Position, GridView<'a, T>)> {
+        assert!(size > 0, "window size must be non-zero");
+
+        let columns = (self.width + 1).saturating_sub(size);
+        let rows = (self.height + 1).saturating_sub(size);
+
+        (0..rows).flat_map(move |y| {
+            (0..columns).map(move |x| {
+                let origin = Position::new(x, y);
+
+                (origin, self.crop(origin, size, size))
+            })
+        })
+    }
+
+    /// Copies the view into a grid of its own.
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_vec(self.width, self.cells().map(|(_, c)| c.clone()).collect())
+    }
+
+    fn last_x(&self) -> isize {
+        self.width.saturating_sub(1) as isize
+    }
+
+    fn last_y(&self) -> isize {
+        self.height.saturating_sub(1) as isize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::{
+        grid::Grid,
+        point::{Point, Position},
+    };
+
+    const EXAMPLE_INPUT: &str = "abc\ndef";
+
+    fn transformed(f: impl Fn(super::GridView<char>) -> super::GridView<char>) -> Grid<char> {
+        f(Grid::from(EXAMPLE_INPUT).view()).to_grid()
+    }
+
+    #[test]
+    fn transpose() {
+        assert_eq!(transformed(|v| v.transpose()), Grid::from("ad\nbe\ncf"));
+    }
+
+    #[test]
+    fn rotations() {
+        assert_eq!(transformed(|v| v.rotate_90()), Grid::from("da\neb\nfc"));
+        assert_eq!(transformed(|v| v.rotate_180()), Grid::from("fed\ncba"));
+        assert_eq!(transformed(|v| v.rotate_270()), Grid::from("cf\nbe\nad"));
+        assert_eq!(
+            transformed(|v| v.rotate_90().rotate_90().rotate_90().rotate_90()),
+            Grid::from(EXAMPLE_INPUT)
+        );
+    }
+
+    #[test]
+    fn flips() {
+        assert_eq!(transformed(|v| v.flip_horizontal()), Grid::from("cba\nfed"));
+        assert_eq!(transformed(|v| v.flip_vertical()), Grid::from("def\nabc"));
+    }
+
+    #[test]
+    fn crop_is_clipped() {
+        assert_eq!(
+            transformed(|v| v.crop(Position::new(1, 0), 5, 1)),
+            Grid::from("bc")
+        );
+        assert!(transformed(|v| v.crop(Position::new(4, 4), 2, 2)).is_empty());
+    }
+
+    #[test]
+    fn transforms_compose() {
+        let grid = Grid::from(EXAMPLE_INPUT);
+        let view = grid.view().rotate_90().crop(Position::new(0, 1), 2, 2);
+
+        assert_eq!(view.to_grid(), Grid::from("eb\nfc"));
+        assert_eq!(view.get(Point(1, 0)), Some(&'b'));
+        assert_eq!(view.source(Point(1, 0)), Some(Position::new(1, 0)));
+        assert_eq!(view.get(Point(2, 0)), None);
+    }
+
+    #[test]
+    fn rows_and_columns_of_a_view() {
+        let grid = Grid::from(EXAMPLE_INPUT);
+        let view = grid.view().flip_horizontal();
+
+        assert_eq!(view.row(1).unwrap().collect::<String>(), "fed");
+        assert_eq!(view.column(0).unwrap().collect::<String>(), "cf");
+        assert!(view.row(2).is_none());
+    }
+
+    #[test]
+    fn sliding_windows() {
+        let grid = Grid::from("abc\ndef\nghi");
+
+        let expected = vec![
+            (Position::new(0, 0), Grid::from("ab\nde")),
+            (Position::new(1, 0), Grid::from("bc\nef")),
+            (Position::new(0, 1), Grid::from("de\ngh")),
+            (Position::new(1, 1), Grid::from("ef\nhi")),
+        ];
+        let actual = grid
+            .view()
+            .windows(2)
+            .map(|(origin, window)| (origin, window.to_grid()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(actual, expected);
+        assert_eq!(grid.view().windows(4).count(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn empty_windows_are_rejected() {
+        let _ = Grid::from("ab").view().windows(0);
+    }
+}
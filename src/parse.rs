@@ -0,0 +1,398 @@
+//! Locating errors in puzzle inputs, and helpers for the usual input formats.
+//!
+//! Helpers take the whole `input` along with the slice of it they work on, so
+//! errors can point at the right line of the input.
+
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use crate::models::grid::Grid;
+
+/// Why a puzzle input couldn't be parsed, and where.
+///
+/// Lines and columns count from one, columns in characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Filled in once the error leaves the day's parser, see [`ParseError::in_day`].
+    pub day: Option<u8>,
+    pub line: usize,
+    pub column: usize,
+    /// The part of the line that couldn't be parsed, empty when something was missing.
+    pub text: String,
+    pub message: String,
+    source_line: String,
+}
+
+impl ParseError {
+    /// An error about `span`, which has to be a slice of `input`.
+    pub fn at(input: &str, span: &str, message: impl Into<String>) -> Self {
+        let offset = offset_of(input, span);
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |i| offset + i);
+
+        Self {
+            day: None,
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            text: span.to_string(),
+            message: message.into(),
+            source_line: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+        }
+    }
+
+    /// Moves an error found in a line parsed on its own, such as one read
+    /// from a stream, to that line of the whole input.
+    pub fn on_line(self, line: usize) -> Self {
+        Self { line, ..self }
+    }
+
+    pub fn in_day(self, day: u8) -> Self {
+        Self {
+            day: Some(day),
+            ..self
+        }
+    }
+
+    /// The error followed by the offending line, with the text underlined:
+    ///
+    /// ```text
+    /// day 1, line 2, column 5: invalid number `4x`
+    ///   |
+    /// 2 | 3   4x
+    ///   |     ^^
+    /// ```
+    pub fn diagnostic(&self) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        let underline = "^".repeat(self.text.chars().count().max(1));
+
+        format!(
+            "{}\n{} |\n{} | {}\n{} | {}{}",
+            self,
+            gutter,
+            self.line,
+            self.source_line,
+            gutter,
+            " ".repeat(self.column - 1),
+            underline
+        )
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = self.day {
+            write!(f, "day {}, ", day)?;
+        }
+
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Where `span`, which has to be a slice of `input`, starts in `input`.
+fn offset_of(input: &str, span: &str) -> usize {
+    let start = span.as_ptr() as usize;
+    let base = input.as_ptr() as usize;
+
+    assert!(
+        start >= base && start + span.len() <= base + input.len(),
+        "`{}` isn't part of the input",
+        span
+    );
+
+    start - base
+}
+
+/// Parses `token`, a slice of `input`, as a number.
+pub fn number<T: FromStr>(input: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(input, token, format!("invalid number `{}`", token)))
+}
+
+/// Every unsigned integer in `line`, skipping whatever is between them. Minus
+/// signs are skipped like any other separator.
+pub fn integers<T: FromStr>(input: &str, line: &str) -> Result<Vec<T>, ParseError> {
+    integer_tokens(line, false)
+        .map(|token| number(input, token))
+        .collect()
+}
+
+/// Every integer in `line`, where a `-` right before the digits makes it
+/// negative.
+pub fn signed_integers<T: FromStr>(input: &str, line: &str) -> Result<Vec<T>, ParseError> {
+    integer_tokens(line, true)
+        .map(|token| number(input, token))
+        .collect()
+}
+
+fn integer_tokens(line: &str, signed: bool) -> impl Iterator<Item = &str> {
+    let bytes = line.as_bytes();
+    let mut i = 0;
+
+    std::iter::from_fn(move || {
+        while i < bytes.len() {
+            let start = i;
+
+            if signed && bytes[i] == b'-' && bytes.get(i + 1).is_some_and(u8::is_ascii_digit) {
+                i += 1;
+            }
+
+            if bytes[i].is_ascii_digit() {
+                while bytes.get(i).is_some_and(u8::is_ascii_digit) {
+                    i += 1;
+                }
+
+                return Some(&line[start..i]);
+            }
+
+            i += 1;
+        }
+
+        None
+    })
+}
+
+/// The blocks of lines in `input` separated by blank lines, without the
+/// surrounding newlines.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    let mut lines = input.lines().peekable();
+
+    std::iter::from_fn(move || {
+        while lines.next_if(|line| line.trim().is_empty()).is_some() {}
+
+        let first = lines.next()?;
+        let mut last = first;
+
+        while let Some(line) = lines.next_if(|line| !line.trim().is_empty()) {
+            last = line;
+        }
+
+        Some(&input[offset_of(input, first)..offset_of(input, last) + last.len()])
+    })
+}
+
+/// Splits a `key<separator>values` line such as `190: 10 19`, trimming both
+/// halves.
+pub fn key_values<'a>(
+    input: &str,
+    line: &'a str,
+    separator: &str,
+) -> Result<(&'a str, &'a str), ParseError> {
+    line.split_once(separator)
+        .map(|(key, values)| (key.trim(), values.trim()))
+        .ok_or_else(|| {
+            ParseError::at(
+                input,
+                line,
+                format!("expected `<key>{}<values>`", separator),
+            )
+        })
+}
+
+/// Parses every item of `text` separated by `delimiter`. Items are trimmed,
+/// and a whitespace delimiter splits on any run of whitespace.
+pub fn list<T: FromStr>(input: &str, text: &str, delimiter: &str) -> Result<Vec<T>, ParseError> {
+    let items: Box<dyn Iterator<Item = &str>> = if delimiter.trim().is_empty() {
+        Box::new(text.split_whitespace())
+    } else {
+        Box::new(text.split(delimiter).map(str::trim))
+    };
+
+    items
+        .map(|item| {
+            item.parse()
+                .map_err(|_| ParseError::at(input, item, format!("invalid value `{}`", item)))
+        })
+        .collect()
+}
+
+/// Every character of `text` as a digit, such as `2333133121414131402`.
+pub fn digits(input: &str, text: &str) -> Result<Vec<u8>, ParseError> {
+    text.char_indices()
+        .map(|(i, c)| {
+            c.to_digit(10).map(|digit| digit as u8).ok_or_else(|| {
+                let span = &text[i..i + c.len_utf8()];
+
+                ParseError::at(input, span, format!("invalid digit `{}`", c))
+            })
+        })
+        .collect()
+}
+
+/// Parses `text` as a grid with one cell per character, where `cell` rejects
+/// a character by returning `None`. Lines all have to be the same length.
+pub fn grid<T>(
+    input: &str,
+    text: &str,
+    mut cell: impl FnMut(char) -> Option<T>,
+) -> Result<Grid<T>, ParseError> {
+    let mut width = None;
+    let mut cells = Vec::with_capacity(text.len());
+
+    for line in text.lines() {
+        let before = cells.len();
+
+        for (i, c) in line.char_indices() {
+            let span = &line[i..i + c.len_utf8()];
+
+            cells.push(cell(c).ok_or_else(|| {
+                ParseError::at(input, span, format!("unexpected character `{}`", c))
+            })?);
+        }
+
+        let line_width = cells.len() - before;
+
+        match width {
+            None => width = Some(line_width),
+            Some(width) if width != line_width => {
+                // Point at the extra cells, or just past the end of a short line.
+                let extra = line
+                    .char_indices()
+                    .nth(width)
+                    .map_or(line.len(), |(i, _)| i);
+
+                return Err(ParseError::at(
+                    input,
+                    &line[extra..],
+                    format!("{} wide instead of {}", line_width, width),
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok(Grid::from_vec(width.unwrap_or(0), cells))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::point::Position;
+
+    use super::{
+        digits, grid, integers, key_values, list, number, sections, signed_integers, ParseError,
+    };
+
+    const EXAMPLE_INPUT: &str = "1   2\n3   4x\n";
+
+    #[test]
+    fn locates_the_offending_text() {
+        let expected = (2, 5, "4x".to_string());
+
+        let error = number::<u32>(EXAMPLE_INPUT, &EXAMPLE_INPUT[10..12]).unwrap_err();
+        let actual = (error.line, error.column, error.text);
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn points_at_the_offending_text() {
+        let error = number::<u32>(EXAMPLE_INPUT, &EXAMPLE_INPUT[10..12])
+            .unwrap_err()
+            .in_day(1);
+
+        let expected = "day 1, line 2, column 5: invalid number `4x`\n  |\n2 | 3   4x\n  |     ^^";
+        let actual = error.diagnostic();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn missing_text_gets_a_single_caret() {
+        let line = &EXAMPLE_INPUT[..5];
+        let error = ParseError::at(EXAMPLE_INPUT, &line[5..], "expected a second number");
+
+        assert_eq!(error.column, 6);
+        assert!(error.diagnostic().ends_with("1   2\n  |      ^"));
+    }
+
+    #[test]
+    fn integers_on_a_line() {
+        let line = "p=0,4 v=3,-3 x12";
+
+        assert_eq!(integers::<u32>(line, line), Ok(vec![0, 4, 3, 3, 12]));
+        assert_eq!(
+            signed_integers::<i32>(line, line),
+            Ok(vec![0, 4, 3, -3, 12])
+        );
+        assert_eq!(
+            signed_integers::<i32>("a-b 1-2", "a-b 1-2"),
+            Ok(vec![1, -2])
+        );
+    }
+
+    #[test]
+    fn integers_that_overflow_are_located() {
+        let input = "1\n2 300";
+
+        let err = integers::<u8>(input, &input[2..]).unwrap_err();
+
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "300"));
+    }
+
+    #[test]
+    fn sections_are_split_on_blank_lines() {
+        let input = "\na|b\nc|d\n\n  \n1,2\n";
+
+        let expected = vec!["a|b\nc|d", "1,2"];
+        let actual = sections(input).collect::<Vec<&str>>();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn key_and_values() {
+        let input = "190: 10 19\n3267 81";
+
+        let (key, values) = key_values(input, &input[..10], ":").unwrap();
+
+        assert_eq!(number::<u64>(input, key), Ok(190));
+        assert_eq!(list::<u64>(input, values, " "), Ok(vec![10, 19]));
+
+        let err = key_values(input, &input[11..], ":").unwrap_err();
+
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn delimited_lists() {
+        let input = "75, 47,61\n1|x";
+
+        assert_eq!(list::<u32>(input, &input[..9], ","), Ok(vec![75, 47, 61]));
+
+        let err = list::<u32>(input, &input[10..], "|").unwrap_err();
+
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 3, "x"));
+    }
+
+    #[test]
+    fn digit_strings() {
+        assert_eq!(digits("12345", "12345"), Ok(vec![1, 2, 3, 4, 5]));
+
+        let err = digits("12a45", "12a45").unwrap_err();
+
+        assert_eq!(err.column, 3);
+    }
+
+    #[test]
+    fn grids_in_a_section() {
+        let input = "rules\n\n#.\n.#";
+        let section = sections(input).nth(1).unwrap();
+
+        let grid = grid(input, section, |c| Some(c == '#')).unwrap();
+
+        assert!(grid[Position::new(1, 1)]);
+
+        let err = super::grid(input, section, |c| (c == '.').then_some(c)).unwrap_err();
+
+        assert_eq!((err.line, err.column), (3, 1));
+    }
+}
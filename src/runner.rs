@@ -0,0 +1,33 @@
+use std::time::Instant;
+
+use crate::{
+    input::InputSource,
+    parse::ParseError,
+    solution::{Part, Puzzle},
+};
+
+pub fn run(puzzle: &dyn Puzzle, input: &str, parts: &[Part]) -> Result<(), ParseError> {
+    println!("===== DAY {} =====", puzzle.day());
+
+    for part in parts {
+        let now = Instant::now();
+        let answer = puzzle.solve(input, *part)?;
+        println!("({:.2?}) Part {}: {}", now.elapsed(), part, answer);
+    }
+
+    Ok(())
+}
+
+/// Runs `parts` of each puzzle on its input from `source`, stopping at the
+/// first input that can't be read or parsed.
+pub fn run_all(
+    puzzles: &[&dyn Puzzle],
+    source: &InputSource,
+    parts: &[Part],
+) -> Result<(), String> {
+    for puzzle in puzzles {
+        run(*puzzle, &source.load_day(*puzzle)?, parts).map_err(|err| err.diagnostic())?;
+    }
+
+    Ok(())
+}
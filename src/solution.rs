@@ -0,0 +1,79 @@
+use std::fmt::Display;
+
+use crate::parse::ParseError;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A single day's puzzle: how to parse the input and how to answer both parts.
+pub trait Solution {
+    const DAY: u8;
+    const PUZZLE_INPUT: &'static str;
+
+    type Input;
+    type Answer: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part_one(input: &Self::Input) -> Self::Answer;
+
+    fn part_two(input: &Self::Input) -> Self::Answer;
+
+    /// Parses and solves part one, panicking with the diagnostic when the
+    /// input is invalid.
+    fn solve_part_one(input: &str) -> Self::Answer {
+        Self::part_one(&Self::parse_or_panic(input))
+    }
+
+    fn solve_part_two(input: &str) -> Self::Answer {
+        Self::part_two(&Self::parse_or_panic(input))
+    }
+
+    fn parse_or_panic(input: &str) -> Self::Input {
+        Self::parse(input).unwrap_or_else(|err| panic!("{}", err.in_day(Self::DAY).diagnostic()))
+    }
+}
+
+/// Object safe view of a [`Solution`], so the registry can hold every day in one list.
+pub trait Puzzle: Sync {
+    fn day(&self) -> u8;
+
+    fn puzzle_input(&self) -> &'static str;
+
+    fn solve(&self, input: &str, part: Part) -> Result<String, ParseError>;
+}
+
+impl<S: Solution + Sync> Puzzle for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn puzzle_input(&self) -> &'static str {
+        S::PUZZLE_INPUT
+    }
+
+    fn solve(&self, input: &str, part: Part) -> Result<String, ParseError> {
+        let input = S::parse(input).map_err(|err| err.in_day(S::DAY))?;
+
+        Ok(match part {
+            Part::One => S::part_one(&input).to_string(),
+            Part::Two => S::part_two(&input).to_string(),
+        })
+    }
+}
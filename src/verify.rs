@@ -0,0 +1,295 @@
+use std::{fmt::Display, fs, path::Path};
+
+use crate::{
+    input::InputSource,
+    parse::ParseError,
+    solution::{Part, Puzzle},
+};
+
+/// Answers for the embedded puzzle inputs, used when `--answers` isn't given.
+pub const EMBEDDED_ANSWERS: &str = include_str!("../answers.txt");
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct KnownAnswer {
+    pub day: u8,
+    pub part: Part,
+    pub input_hash: u64,
+    pub answer: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    Missing {
+        actual: String,
+    },
+    /// The input couldn't be read or parsed, counted as a failure.
+    Error {
+        message: String,
+    },
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Pass => write!(f, "PASS"),
+            Outcome::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {}, got {})", expected, actual)
+            }
+            Outcome::Missing { actual } => write!(f, "MISSING (got {})", actual),
+            Outcome::Error { message } => write!(f, "ERROR\n{}", message),
+        }
+    }
+}
+
+/// Running tally of outcomes for a `verify` run.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    pub passed: usize,
+    pub failed: usize,
+    pub missing: usize,
+}
+
+impl Summary {
+    pub fn record(&mut self, outcome: &Outcome) {
+        match outcome {
+            Outcome::Pass => self.passed += 1,
+            Outcome::Fail { .. } | Outcome::Error { .. } => self.failed += 1,
+            Outcome::Missing { .. } => self.missing += 1,
+        }
+    }
+
+    pub fn has_failures(&self) -> bool {
+        self.failed > 0
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} passed, {} failed, {} missing",
+            self.passed, self.failed, self.missing
+        )
+    }
+}
+
+/// Reads the answers file at `path`, or the embedded answers without one.
+pub fn load_answers(path: Option<&Path>) -> Result<Vec<KnownAnswer>, String> {
+    match path {
+        Some(path) => parse_answers(
+            &fs::read_to_string(path)
+                .map_err(|err| format!("failed to read {}: {}", path.display(), err))?,
+        ),
+        None => parse_answers(EMBEDDED_ANSWERS),
+    }
+}
+
+/// Parses an answers file. Each non-empty line that isn't a `#` comment holds
+/// `<day> <part> <input hash> <answer>`, with the hash written in hex as
+/// produced by [`hash_input`].
+pub fn parse_answers(contents: &str) -> Result<Vec<KnownAnswer>, String> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line_number, line)| {
+            parse_answer(line).map_err(|err| format!("line {}: {}", line_number, err))
+        })
+        .collect()
+}
+
+fn parse_answer(line: &str) -> Result<KnownAnswer, String> {
+    let fields = line.split_whitespace().collect::<Vec<&str>>();
+
+    let [day, part, input_hash, answer] = fields[..] else {
+        return Err(format!(
+            "expected `<day> <part> <input hash> <answer>`, got `{}`",
+            line
+        ));
+    };
+
+    let day = day.parse().map_err(|_| format!("invalid day `{}`", day))?;
+    let part = match part {
+        "1" => Part::One,
+        "2" => Part::Two,
+        other => return Err(format!("invalid part `{}`", other)),
+    };
+    let input_hash = u64::from_str_radix(input_hash, 16)
+        .map_err(|_| format!("invalid input hash `{}`", input_hash))?;
+
+    Ok(KnownAnswer {
+        day,
+        part,
+        input_hash,
+        answer: answer.to_string(),
+    })
+}
+
+/// FNV-1a hash of the input. Unlike `DefaultHasher` it is stable across Rust
+/// versions, so it can be written into the answers file.
+pub fn hash_input(input: &str) -> u64 {
+    input.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+pub fn verify(
+    puzzle: &dyn Puzzle,
+    input: &str,
+    part: Part,
+    answers: &[KnownAnswer],
+) -> Result<Outcome, ParseError> {
+    let input_hash = hash_input(input);
+    let actual = puzzle.solve(input, part)?;
+
+    let known = answers.iter().find(|known| {
+        known.day == puzzle.day() && known.part == part && known.input_hash == input_hash
+    });
+
+    Ok(match known {
+        Some(known) if known.answer == actual => Outcome::Pass,
+        Some(known) => Outcome::Fail {
+            expected: known.answer.clone(),
+            actual,
+        },
+        None => Outcome::Missing { actual },
+    })
+}
+
+/// Verifies both parts of each puzzle on its input from `source`, printing
+/// every outcome and then the summary. A day that can't be read or parsed
+/// fails without stopping the others.
+pub fn verify_all(
+    puzzles: &[&dyn Puzzle],
+    source: &InputSource,
+    answers: &[KnownAnswer],
+) -> Summary {
+    let mut summary = Summary::default();
+
+    for puzzle in puzzles {
+        let input = source.load_day(*puzzle);
+
+        for part in Part::ALL {
+            let outcome = match &input {
+                Ok(input) => {
+                    verify(*puzzle, input, part, answers).unwrap_or_else(|err| Outcome::Error {
+                        message: err.diagnostic(),
+                    })
+                }
+                Err(message) => Outcome::Error {
+                    message: message.clone(),
+                },
+            };
+
+            println!("Day {} Part {}: {}", puzzle.day(), part, outcome);
+
+            summary.record(&outcome);
+        }
+    }
+
+    println!("{}", summary);
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        hash_input, parse_answers, verify, KnownAnswer, Outcome, Summary, EMBEDDED_ANSWERS,
+    };
+    use crate::{day1::DayOne, solution::Part};
+
+    const EXAMPLE_INPUT: &str = include_str!("./day1/example_input.txt");
+
+    fn answer(part: Part, answer: &str) -> KnownAnswer {
+        KnownAnswer {
+            day: 1,
+            part,
+            input_hash: hash_input(EXAMPLE_INPUT),
+            answer: answer.to_string(),
+        }
+    }
+
+    #[test]
+    fn hash_is_stable() {
+        assert_eq!(hash_input(""), 0xcbf29ce484222325);
+        assert_eq!(hash_input("a"), 0xaf63dc4c8601ec8c);
+    }
+
+    #[test]
+    fn parses_answers_and_skips_comments() {
+        let answers = parse_answers("# day part hash answer\n\n6 2 ff 1586\n").unwrap();
+
+        assert_eq!(
+            answers,
+            vec![KnownAnswer {
+                day: 6,
+                part: Part::Two,
+                input_hash: 0xff,
+                answer: "1586".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_line_of_malformed_answer() {
+        let err = parse_answers("1 1 ff 11\n1 3 ff 31").unwrap_err();
+
+        assert!(err.starts_with("line 2:"), "{}", err);
+    }
+
+    #[test]
+    fn embedded_answers_parse() {
+        assert!(!parse_answers(EMBEDDED_ANSWERS).unwrap().is_empty());
+    }
+
+    #[test]
+    fn verify_reports_pass_fail_and_missing() {
+        let answers = vec![answer(Part::One, "11"), answer(Part::Two, "30")];
+
+        assert_eq!(
+            verify(&DayOne, EXAMPLE_INPUT, Part::One, &answers),
+            Ok(Outcome::Pass)
+        );
+        assert_eq!(
+            verify(&DayOne, EXAMPLE_INPUT, Part::Two, &answers),
+            Ok(Outcome::Fail {
+                expected: "30".to_string(),
+                actual: "31".to_string()
+            })
+        );
+        assert_eq!(
+            verify(&DayOne, "1 1", Part::One, &answers),
+            Ok(Outcome::Missing {
+                actual: "0".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn verify_reports_invalid_input() {
+        let err = verify(&DayOne, "1 x", Part::One, &[]).unwrap_err();
+
+        assert_eq!(err.day, Some(1));
+        assert_eq!((err.line, err.column), (1, 3));
+    }
+
+    #[test]
+    fn errors_count_as_failures() {
+        let mut summary = Summary::default();
+
+        summary.record(&Outcome::Pass);
+        summary.record(&Outcome::Error {
+            message: "line 1, column 3: invalid number `x`".to_string(),
+        });
+
+        assert_eq!(summary.failed, 1);
+        assert!(summary.has_failures());
+    }
+}
@@ -0,0 +1,38 @@
+use aoc_2024::{
+    day1::DayOne,
+    days,
+    input::InputSource,
+    models::{matrix::Matrix, point::Point},
+    solution::{Part, Solution},
+    verify::{self, Outcome},
+};
+
+#[test]
+fn solutions_are_usable_outside_the_crate() {
+    let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3";
+
+    assert_eq!(DayOne::solve_part_one(input), 11);
+    assert_eq!(
+        days::find(1).unwrap().solve(input, Part::Two),
+        Ok("31".to_string())
+    );
+}
+
+#[test]
+fn models_are_usable_outside_the_crate() {
+    let matrix = Matrix::from("ab\ncd");
+
+    assert_eq!(matrix.get(1, 1), "d");
+    assert_eq!(Point(1, 2) + Point(2, 1), Point(3, 3));
+}
+
+#[test]
+fn embedded_inputs_can_be_verified_outside_the_crate() {
+    let input = InputSource::Embedded.load(&DayOne).unwrap();
+    let answers = verify::load_answers(None).unwrap();
+
+    assert_eq!(
+        verify::verify(&DayOne, &input, Part::One, &answers),
+        Ok(Outcome::Pass)
+    );
+}
@@ -0,0 +1,308 @@
+use proc_macro::TokenStream;
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{
+    meta::ParseNestedMeta, parse::Parser, Attribute, Block, ImplItem, Item, ItemImpl, LitInt,
+    LitStr, ReturnType, Signature, Type,
+};
+
+#[derive(Default, Clone)]
+struct Args {
+    iterations: Option<usize>,
+    warmup: Option<usize>,
+    label: Option<LitStr>,
+}
+
+impl Args {
+    fn parse(&mut self, meta: ParseNestedMeta) -> syn::Result<()> {
+        if meta.path.is_ident("iterations") {
+            let iterations = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+
+            if iterations == 0 {
+                return Err(meta.error("iterations must be at least 1"));
+            }
+
+            self.iterations = Some(iterations);
+            Ok(())
+        } else if meta.path.is_ident("warmup") {
+            self.warmup = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+            Ok(())
+        } else if meta.path.is_ident("label") {
+            self.label = Some(meta.value()?.parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("expected `iterations`, `warmup` or `label`"))
+        }
+    }
+
+    fn is_benchmark(&self) -> bool {
+        self.iterations.is_some() || self.warmup.is_some()
+    }
+}
+
+/// Prints how long each call to the function takes. Calls made while another
+/// timed function is running are only added to the span summary.
+///
+/// Works on free functions, generic functions, `async fn` (timed from the first
+/// poll until completion, across awaits) and methods. Methods taking `self` are
+/// labelled `Type::method`; putting the attribute on a whole `impl` block
+/// instruments every function in it with the same kind of label. An explicit
+/// label can be given with `#[time_it(label = "...")]`.
+///
+/// `#[time_it(iterations = N, warmup = W)]` turns the function into a
+/// benchmark: the body runs `W` times unmeasured and `N` times measured, the
+/// min/median/mean/p95/stddev are printed and the result of the first run is
+/// returned. The body has to be callable more than once, so it can't move out
+/// of its arguments, and it can't be `async`.
+///
+/// Without the `enabled` feature the function is left exactly as written.
+#[proc_macro_attribute]
+pub fn time_it(attr: TokenStream, item: TokenStream) -> TokenStream {
+    expand(cfg!(feature = "enabled"), attr.into(), item.into()).into()
+}
+
+fn expand(enabled: bool, attr: TokenStream2, item: TokenStream2) -> TokenStream2 {
+    // Arguments are checked even when timing is off, so a typo doesn't only
+    // show up once the feature is turned back on.
+    let mut args = Args::default();
+    let parser = syn::meta::parser(|meta| args.parse(meta));
+
+    if let Err(err) = parser.parse2(attr) {
+        return err.to_compile_error();
+    }
+
+    if !enabled {
+        return item;
+    }
+
+    let expanded = match syn::parse2::<Item>(item) {
+        Ok(Item::Fn(item)) => {
+            let label = fn_label(&args, &item.sig, None);
+            instrument(&args, &item.attrs, &item.vis, &item.sig, &item.block, label)
+        }
+        Ok(Item::Impl(item)) => instrument_impl(&args, item),
+        Ok(item) => Err(syn::Error::new_spanned(
+            item,
+            "#[time_it] can only be used on functions, methods and impl blocks",
+        )),
+        Err(err) => Err(err),
+    };
+
+    expanded.unwrap_or_else(|err| err.to_compile_error())
+}
+
+fn instrument_impl(args: &Args, mut item: ItemImpl) -> syn::Result<TokenStream2> {
+    if args.label.is_some() {
+        return Err(syn::Error::new_spanned(
+            &item.self_ty,
+            "`label` can't be used on an impl block, put it on the method instead",
+        ));
+    }
+
+    let type_name = type_label(&item.self_ty);
+
+    for impl_item in &mut item.items {
+        let ImplItem::Fn(method) = impl_item else {
+            continue;
+        };
+
+        // Methods with their own attribute are expanded by it.
+        if method.attrs.iter().any(is_time_it) {
+            continue;
+        }
+
+        let label = fn_label(args, &method.sig, Some(&type_name));
+        let tokens = instrument(
+            args,
+            &method.attrs,
+            &method.vis,
+            &method.sig,
+            &method.block,
+            label,
+        )?;
+
+        *method = syn::parse2(tokens)?;
+    }
+
+    Ok(item.into_token_stream())
+}
+
+fn is_time_it(attr: &Attribute) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == "time_it")
+}
+
+/// Last path segment of the implementing type, without generics.
+fn type_label(ty: &Type) -> String {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string())
+            .unwrap_or_default(),
+        Type::Reference(reference) => type_label(&reference.elem),
+        other => other.to_token_stream().to_string(),
+    }
+}
+
+fn fn_label(args: &Args, sig: &Signature, type_name: Option<&str>) -> TokenStream2 {
+    let fn_name = sig.ident.to_string();
+
+    if let Some(label) = &args.label {
+        return quote! { #label };
+    }
+
+    if let Some(type_name) = type_name {
+        let label = format!("{}::{}", type_name, fn_name);
+        return quote! { #label };
+    }
+
+    // Outside of an impl block the type is only known at runtime, through
+    // `Self`, and only methods with a receiver are guaranteed to have one.
+    if sig.receiver().is_some() {
+        return quote! {
+            ::time_it::__private::method_label(::std::any::type_name::<Self>(), #fn_name)
+        };
+    }
+
+    quote! { #fn_name }
+}
+
+fn instrument(
+    args: &Args,
+    attrs: &[Attribute],
+    vis: &syn::Visibility,
+    sig: &Signature,
+    block: &Block,
+    label: TokenStream2,
+) -> syn::Result<TokenStream2> {
+    if !args.is_benchmark() {
+        // The body is kept in place rather than moved into a closure, so
+        // `return`, `?`, `impl Trait` returns and borrowed arguments behave
+        // exactly as written. The span is closed when `_span` is dropped.
+        let span = if sig.asyncness.is_some() {
+            quote! { ::time_it::__private::Span::detached(#label) }
+        } else {
+            quote! { ::time_it::__private::Span::enter(#label) }
+        };
+
+        return Ok(quote! {
+            #(#attrs)*
+            #vis #sig {
+                let _span = #span;
+                #block
+            }
+        });
+    }
+
+    if let Some(asyncness) = &sig.asyncness {
+        return Err(syn::Error::new_spanned(
+            asyncness,
+            "`iterations` and `warmup` can't be used on an async fn",
+        ));
+    }
+
+    let iterations = args.iterations.unwrap_or(1);
+    let warmup = args.warmup.unwrap_or(0);
+
+    // The closure gets the function's return type so `?` converts errors the
+    // same way, unless it is an `impl Trait` which closures can't name.
+    let return_type = match &sig.output {
+        ReturnType::Type(_, ty) if !mentions_impl(ty) => quote! { -> #ty },
+        _ => quote! {},
+    };
+
+    // The first call doubles as the first warmup run, or as the first sample
+    // when there is no warmup, so its result can be handed back to the caller.
+    let first_call = if warmup == 0 {
+        quote! {
+            let start = ::time_it::__private::Instant::now();
+            let result = body();
+            samples.push(start.elapsed());
+        }
+    } else {
+        let remaining_warmup = warmup - 1;
+
+        quote! {
+            let result = body();
+
+            for _ in 0..#remaining_warmup {
+                let _ = ::time_it::__private::black_box(body());
+            }
+        }
+    };
+
+    Ok(quote! {
+        #(#attrs)*
+        #vis #sig {
+            #[allow(unused_mut)]
+            let mut body = || #return_type #block;
+            let mut samples = ::std::vec::Vec::with_capacity(#iterations);
+
+            #first_call
+
+            while samples.len() < #iterations {
+                let start = ::time_it::__private::Instant::now();
+                let _ = ::time_it::__private::black_box(body());
+                samples.push(start.elapsed());
+            }
+
+            ::time_it::__private::report_samples(#label, &samples);
+            result
+        }
+    })
+}
+
+fn mentions_impl(ty: &Type) -> bool {
+    fn walk(tokens: TokenStream2) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => ident == "impl",
+            TokenTree::Group(group) => walk(group.stream()),
+            _ => false,
+        })
+    }
+
+    walk(ty.to_token_stream())
+}
+
+#[cfg(test)]
+mod tests {
+    use quote::quote;
+
+    use super::expand;
+
+    #[test]
+    fn disabled_leaves_the_function_unchanged() {
+        let item = quote! {
+            fn add(a: u32, b: u32) -> u32 {
+                a + b
+            }
+        };
+
+        let expected = item.to_string();
+        let actual = expand(false, quote! { iterations = 10 }, item).to_string();
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn disabled_still_checks_arguments() {
+        let item = quote! { fn noop() {} };
+
+        let actual = expand(false, quote! { repeat = 3 }, item).to_string();
+
+        assert!(actual.contains("compile_error"));
+    }
+
+    #[test]
+    fn enabled_opens_a_span() {
+        let item = quote! { fn noop() {} };
+
+        let actual = expand(true, quote! {}, item).to_string();
+
+        assert!(actual.contains("Span :: enter"));
+    }
+}
@@ -1,26 +1,50 @@
-use proc_macro::TokenStream;
-use quote::quote;
-use syn::{parse_macro_input, ItemFn};
+//! Timing instrumentation for functions.
+//!
+//! `#[time_it]` prints how long a single call took. With
+//! `#[time_it(iterations = 100, warmup = 10)]` the body is re-run and a
+//! [`Stats`] summary is printed instead, while the caller still gets the result
+//! of the first call.
+//!
+//! Measurements go to stdout unless another [`Sink`] is chosen with
+//! [`set_sink`] or the `TIME_IT_SINK` environment variable. Calls faster than
+//! `TIME_IT_THRESHOLD_MS` milliseconds, or [`set_threshold`], are left out.
+//!
+//! The attribute only instruments anything with the `enabled` feature, which
+//! is on by default. Without it functions are compiled exactly as written.
+//!
+//! Timed functions called from other timed functions are not reported one by
+//! one. They are aggregated into a per-function call tree instead, which
+//! [`report_span_summary`] prints at the end of a run.
 
-#[proc_macro_attribute]
-pub fn time_it(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(item as ItemFn);
-    let fn_name = &input.sig.ident;
-    let fn_block = &input.block;
-    let fn_attrs = &input.attrs;
-    let fn_vis = &input.vis;
-    let fn_sig = &input.sig;
+mod sink;
+mod spans;
+mod stats;
 
-    let gen = quote! {
-        #(#fn_attrs)*
-        #fn_vis #fn_sig {
-            let start = std::time::Instant::now();
-            let result = (|| #fn_block)();
-            let duration = start.elapsed();
-            println!("Function `{}` took {:?}", stringify!(#fn_name), duration);
-            result
-        }
-    };
+pub use sink::{
+    set_sink, set_threshold, take_measurements, Measurement, Sink, SINK_ENV_VAR, THRESHOLD_ENV_VAR,
+};
+pub use spans::{report_span_summary, reset_spans, span_summary, SpanNode, SpanSummary};
+pub use stats::Stats;
+pub use time_it_macros::time_it;
+
+/// Support code for the expansion of `#[time_it]`, not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    use std::time::Duration;
+
+    pub use std::hint::black_box;
+    pub use std::time::Instant;
 
-    gen.into()
+    pub use crate::spans::{method_label, Span};
+    use crate::{sink, Measurement, Stats};
+
+    pub fn report_samples(name: impl Into<String>, samples: &[Duration]) {
+        if let Some(stats) = Stats::from_samples(samples) {
+            sink::record(Measurement {
+                function: name.into(),
+                duration: stats.mean,
+                stats: Some(stats),
+            });
+        }
+    }
 }
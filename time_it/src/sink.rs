@@ -0,0 +1,306 @@
+use std::{
+    env,
+    fmt::Write as _,
+    fs::{File, OpenOptions},
+    io::{self, Write},
+    mem,
+    path::PathBuf,
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
+
+use crate::{SpanSummary, Stats};
+
+/// Environment variable read on the first measurement when [`set_sink`] hasn't
+/// been called. Accepts `stdout`, `stderr`, `memory` or `jsonl:<path>`.
+pub const SINK_ENV_VAR: &str = "TIME_IT_SINK";
+
+/// Environment variable read on the first measurement when [`set_threshold`]
+/// hasn't been called. Calls faster than this many milliseconds aren't
+/// reported, fractions like `0.5` are allowed.
+pub const THRESHOLD_ENV_VAR: &str = "TIME_IT_THRESHOLD_MS";
+
+/// A single report from a `#[time_it]` function.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    pub function: String,
+    /// How long the call took, or the mean for benchmarked functions.
+    pub duration: Duration,
+    /// Present when the function was benchmarked with `iterations`/`warmup`.
+    pub stats: Option<Stats>,
+}
+
+impl Measurement {
+    fn to_text(&self) -> String {
+        match &self.stats {
+            Some(stats) => format!("Function `{}` {}", self.function, stats),
+            None => format!("Function `{}` took {:?}", self.function, self.duration),
+        }
+    }
+
+    fn to_json(&self) -> String {
+        let mut json = format!(
+            "{{\"function\":\"{}\",\"nanos\":{}",
+            escape(&self.function),
+            self.duration.as_nanos()
+        );
+
+        if let Some(stats) = &self.stats {
+            let _ = write!(
+                json,
+                ",\"iterations\":{},\"min_nanos\":{},\"median_nanos\":{},\"mean_nanos\":{},\"p95_nanos\":{},\"max_nanos\":{},\"stddev_nanos\":{}",
+                stats.iterations,
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.mean.as_nanos(),
+                stats.p95.as_nanos(),
+                stats.max.as_nanos(),
+                stats.stddev.as_nanos()
+            );
+        }
+
+        json.push('}');
+        json
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Where measurements are sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Sink {
+    Stdout,
+    Stderr,
+    /// Kept in memory until collected with [`take_measurements`].
+    Memory,
+    /// Appended to a file as one JSON object per line.
+    JsonLines(PathBuf),
+}
+
+impl Sink {
+    fn from_env() -> Sink {
+        match env::var(SINK_ENV_VAR) {
+            Ok(value) => Sink::parse(&value).unwrap_or_else(|| {
+                eprintln!("time_it: ignoring invalid {}={}", SINK_ENV_VAR, value);
+                Sink::Stdout
+            }),
+            Err(_) => Sink::Stdout,
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Sink> {
+        match value {
+            "stdout" => Some(Sink::Stdout),
+            "stderr" => Some(Sink::Stderr),
+            "memory" => Some(Sink::Memory),
+            other => other
+                .strip_prefix("jsonl:")
+                .filter(|path| !path.is_empty())
+                .map(|path| Sink::JsonLines(PathBuf::from(path))),
+        }
+    }
+}
+
+enum Target {
+    Stdout,
+    Stderr,
+    Memory(Vec<Measurement>),
+    JsonLines(File),
+}
+
+impl Target {
+    fn open(sink: Sink) -> io::Result<Target> {
+        Ok(match sink {
+            Sink::Stdout => Target::Stdout,
+            Sink::Stderr => Target::Stderr,
+            Sink::Memory => Target::Memory(Vec::new()),
+            Sink::JsonLines(path) => {
+                Target::JsonLines(OpenOptions::new().create(true).append(true).open(path)?)
+            }
+        })
+    }
+
+    fn write_summary(&mut self, summary: &SpanSummary) {
+        match self {
+            // Unlike `print!`, a closed pipe doesn't panic the timed program.
+            Target::Stdout => {
+                let _ = write!(io::stdout().lock(), "{}", summary);
+            }
+            Target::Stderr => {
+                let _ = write!(io::stderr().lock(), "{}", summary);
+            }
+            // Tests read the tree directly with `span_summary`.
+            Target::Memory(_) => {}
+            Target::JsonLines(file) => {
+                for (path, node) in summary.collapsed() {
+                    let line = format!(
+                        "{{\"span\":\"{}\",\"calls\":{},\"total_nanos\":{},\"self_nanos\":{}}}",
+                        escape(&path),
+                        node.calls,
+                        node.total.as_nanos(),
+                        node.self_time.as_nanos()
+                    );
+
+                    if let Err(err) = writeln!(file, "{}", line) {
+                        eprintln!("time_it: failed to write span summary: {}", err);
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    fn write(&mut self, measurement: Measurement) {
+        match self {
+            Target::Stdout => {
+                let _ = writeln!(io::stdout().lock(), "{}", measurement.to_text());
+            }
+            Target::Stderr => {
+                let _ = writeln!(io::stderr().lock(), "{}", measurement.to_text());
+            }
+            Target::Memory(measurements) => measurements.push(measurement),
+            Target::JsonLines(file) => {
+                if let Err(err) = writeln!(file, "{}", measurement.to_json()) {
+                    eprintln!("time_it: failed to write measurement: {}", err);
+                }
+            }
+        }
+    }
+}
+
+fn threshold_from_env() -> Option<Duration> {
+    let value = env::var(THRESHOLD_ENV_VAR).ok()?;
+
+    parse_threshold(&value).or_else(|| {
+        eprintln!("time_it: ignoring invalid {}={}", THRESHOLD_ENV_VAR, value);
+        None
+    })
+}
+
+fn parse_threshold(value: &str) -> Option<Duration> {
+    let millis = value.trim().parse::<f64>().ok()?;
+
+    Duration::try_from_secs_f64(millis / 1000.0).ok()
+}
+
+struct State {
+    target: Option<Target>,
+    /// `None` until first read from the environment.
+    threshold: Option<Option<Duration>>,
+}
+
+static STATE: Mutex<State> = Mutex::new(State {
+    target: None,
+    threshold: None,
+});
+
+fn lock() -> MutexGuard<'static, State> {
+    STATE.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+/// Sends all following measurements to `sink`, replacing the previous one.
+pub fn set_sink(sink: Sink) -> io::Result<()> {
+    let target = Target::open(sink)?;
+
+    lock().target = Some(target);
+
+    Ok(())
+}
+
+/// Only reports calls which took at least `threshold`, or every call with
+/// `None`. Benchmarked functions are compared by their mean. The span summary
+/// still includes every call.
+pub fn set_threshold(threshold: Option<Duration>) {
+    lock().threshold = Some(threshold);
+}
+
+/// Drains the measurements collected by [`Sink::Memory`]. Returns nothing for
+/// the other sinks.
+pub fn take_measurements() -> Vec<Measurement> {
+    match lock().target.as_mut() {
+        Some(Target::Memory(measurements)) => mem::take(measurements),
+        _ => Vec::new(),
+    }
+}
+
+fn with_target(f: impl FnOnce(&mut Target)) {
+    f(lock().target.get_or_insert_with(|| {
+        Target::open(Sink::from_env()).unwrap_or_else(|err| {
+            eprintln!("time_it: failed to open sink, using stdout: {}", err);
+            Target::Stdout
+        })
+    }));
+}
+
+pub(crate) fn record(measurement: Measurement) {
+    let threshold = *lock().threshold.get_or_insert_with(threshold_from_env);
+
+    if threshold.is_some_and(|threshold| measurement.duration < threshold) {
+        return;
+    }
+
+    with_target(|target| target.write(measurement));
+}
+
+pub(crate) fn record_summary(summary: &SpanSummary) {
+    with_target(|target| target.write_summary(summary));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::{parse_threshold, Measurement, Sink};
+    use crate::Stats;
+
+    #[test]
+    fn parse_sink() {
+        assert_eq!(Sink::parse("stderr"), Some(Sink::Stderr));
+        assert_eq!(
+            Sink::parse("jsonl:timings.jsonl"),
+            Some(Sink::JsonLines(PathBuf::from("timings.jsonl")))
+        );
+        assert_eq!(Sink::parse("jsonl:"), None);
+        assert_eq!(Sink::parse("file"), None);
+    }
+
+    #[test]
+    fn parse_thresholds() {
+        assert_eq!(parse_threshold("5"), Some(Duration::from_millis(5)));
+        assert_eq!(parse_threshold(" 0.5 "), Some(Duration::from_micros(500)));
+        assert_eq!(parse_threshold("-1"), None);
+        assert_eq!(parse_threshold("fast"), None);
+    }
+
+    #[test]
+    fn single_measurement_as_json() {
+        let measurement = Measurement {
+            function: "part_\"one\"".to_string(),
+            duration: Duration::from_micros(3),
+            stats: None,
+        };
+
+        assert_eq!(
+            measurement.to_json(),
+            r#"{"function":"part_\"one\"","nanos":3000}"#
+        );
+    }
+
+    #[test]
+    fn benchmark_measurement_as_json() {
+        let samples = [Duration::from_nanos(10), Duration::from_nanos(30)];
+        let stats = Stats::from_samples(&samples).unwrap();
+        let measurement = Measurement {
+            function: "blink".to_string(),
+            duration: stats.mean,
+            stats: Some(stats),
+        };
+
+        assert_eq!(
+            measurement.to_json(),
+            r#"{"function":"blink","nanos":20,"iterations":2,"min_nanos":10,"median_nanos":10,"mean_nanos":20,"p95_nanos":30,"max_nanos":30,"stddev_nanos":10}"#
+        );
+    }
+}
@@ -0,0 +1,402 @@
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    fmt::Display,
+    mem,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::sink::{self, Measurement};
+
+/// Aggregated timings of one function at one position in the call tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanNode {
+    pub name: String,
+    pub calls: u64,
+    /// Time spent in the function including nested timed functions. Recursive
+    /// calls are only counted once, by the outermost call.
+    pub total: Duration,
+    /// Time spent in the function itself, excluding nested timed functions.
+    pub self_time: Duration,
+    pub children: Vec<SpanNode>,
+}
+
+/// Call tree of every `#[time_it]` function run so far, across all threads.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpanSummary {
+    pub roots: Vec<SpanNode>,
+}
+
+impl SpanSummary {
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+
+    /// Flattens the tree into `(path, node)` pairs, with the path in the
+    /// collapsed `outer;inner` form used by flame graph tools.
+    pub fn collapsed(&self) -> Vec<(String, &SpanNode)> {
+        fn walk<'a>(prefix: &str, node: &'a SpanNode, out: &mut Vec<(String, &'a SpanNode)>) {
+            let path = if prefix.is_empty() {
+                node.name.clone()
+            } else {
+                format!("{};{}", prefix, node.name)
+            };
+
+            out.push((path.clone(), node));
+
+            for child in &node.children {
+                walk(&path, child, out);
+            }
+        }
+
+        let mut out = Vec::new();
+
+        for root in &self.roots {
+            walk("", root, &mut out);
+        }
+
+        out
+    }
+}
+
+impl Display for SpanSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn write_node(
+            f: &mut std::fmt::Formatter<'_>,
+            node: &SpanNode,
+            depth: usize,
+        ) -> std::fmt::Result {
+            let label = format!("{}{}", "  ".repeat(depth), node.name);
+
+            writeln!(
+                f,
+                "{:<40} {:>8} {:>12.2?} {:>12.2?}",
+                label, node.calls, node.total, node.self_time
+            )?;
+
+            for child in &node.children {
+                write_node(f, child, depth + 1)?;
+            }
+
+            Ok(())
+        }
+
+        writeln!(
+            f,
+            "{:<40} {:>8} {:>12} {:>12}",
+            "function", "calls", "total", "self"
+        )?;
+
+        for root in &self.roots {
+            write_node(f, root, 0)?;
+        }
+
+        Ok(())
+    }
+}
+
+struct Node {
+    name: String,
+    children: Vec<usize>,
+    calls: u64,
+    total: Duration,
+    self_time: Duration,
+}
+
+/// Every node of the tree in one arena, `roots` holds the top level ones.
+#[derive(Default)]
+struct Tree {
+    nodes: Vec<Node>,
+    roots: Vec<usize>,
+}
+
+impl Tree {
+    fn child(&mut self, parent: Option<usize>, name: &str) -> usize {
+        let parent = parent.filter(|parent| *parent < self.nodes.len());
+        let siblings = match parent {
+            Some(parent) => &self.nodes[parent].children,
+            None => &self.roots,
+        };
+
+        if let Some(id) = siblings.iter().find(|id| self.nodes[**id].name == name) {
+            return *id;
+        }
+
+        let id = self.nodes.len();
+
+        self.nodes.push(Node {
+            name: name.to_string(),
+            children: Vec::new(),
+            calls: 0,
+            total: Duration::ZERO,
+            self_time: Duration::ZERO,
+        });
+
+        match parent {
+            Some(parent) => self.nodes[parent].children.push(id),
+            None => self.roots.push(id),
+        }
+
+        id
+    }
+
+    fn is_named(&self, id: usize, name: &str) -> bool {
+        self.nodes.get(id).is_some_and(|node| node.name == name)
+    }
+
+    fn snapshot(&self, id: usize) -> SpanNode {
+        let node = &self.nodes[id];
+
+        SpanNode {
+            name: node.name.clone(),
+            calls: node.calls,
+            total: node.total,
+            self_time: node.self_time,
+            children: node.children.iter().map(|id| self.snapshot(*id)).collect(),
+        }
+    }
+}
+
+static TREE: Mutex<Option<Tree>> = Mutex::new(None);
+
+fn with_tree<R>(f: impl FnOnce(&mut Tree) -> R) -> R {
+    let mut tree = TREE.lock().unwrap_or_else(|err| err.into_inner());
+
+    f(tree.get_or_insert_with(Tree::default))
+}
+
+struct Frame {
+    id: u64,
+    node: usize,
+    recursive: bool,
+    start: Instant,
+    children: Duration,
+}
+
+thread_local! {
+    static STACK: RefCell<Vec<Frame>> = const { RefCell::new(Vec::new()) };
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Marks one call of a timed function, closed when dropped.
+#[doc(hidden)]
+pub struct Span {
+    name: Cow<'static, str>,
+    kind: SpanKind,
+}
+
+enum SpanKind {
+    /// On this thread's call stack, part of the span tree.
+    Nested { id: u64 },
+    /// Timed on its own, for futures which may be polled on any thread.
+    Detached { start: Instant },
+}
+
+impl Span {
+    pub fn enter(name: impl Into<Cow<'static, str>>) -> Span {
+        let name = name.into();
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+
+        STACK.with_borrow_mut(|stack| {
+            let parent = stack.last();
+
+            // Direct recursion is folded into the caller's node, otherwise
+            // recursive helpers like day 11's `blink` nest once per call.
+            let (node, recursive) = match parent {
+                Some(parent) if with_tree(|tree| tree.is_named(parent.node, &name)) => {
+                    (parent.node, true)
+                }
+                _ => (
+                    with_tree(|tree| tree.child(parent.map(|p| p.node), &name)),
+                    false,
+                ),
+            };
+
+            stack.push(Frame {
+                id,
+                node,
+                recursive,
+                start: Instant::now(),
+                children: Duration::ZERO,
+            });
+        });
+
+        Span {
+            name,
+            kind: SpanKind::Nested { id },
+        }
+    }
+
+    /// A span which isn't tied to the current thread, used for `async fn`.
+    /// It is always reported on its own and never nested in the tree.
+    pub fn detached(name: impl Into<Cow<'static, str>>) -> Span {
+        Span {
+            name: name.into(),
+            kind: SpanKind::Detached {
+                start: Instant::now(),
+            },
+        }
+    }
+
+    fn report(&mut self, duration: Duration) {
+        sink::record(Measurement {
+            function: mem::take(&mut self.name).into_owned(),
+            duration,
+            stats: None,
+        });
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        let id = match self.kind {
+            SpanKind::Nested { id } => id,
+            SpanKind::Detached { start } => return self.report(start.elapsed()),
+        };
+
+        let popped = STACK.with_borrow_mut(|stack| {
+            // Usually the top frame, but guards can be dropped out of order
+            // when they are moved around by hand.
+            let position = stack.iter().rposition(|frame| frame.id == id)?;
+            let frame = stack.remove(position);
+            let elapsed = frame.start.elapsed();
+
+            if let Some(parent) = position.checked_sub(1).map(|i| &mut stack[i]) {
+                parent.children += elapsed;
+            }
+
+            with_tree(|tree| {
+                // The tree may have been reset while this call was running.
+                let Some(node) = tree.nodes.get_mut(frame.node) else {
+                    return;
+                };
+
+                node.calls += 1;
+                node.self_time += elapsed.saturating_sub(frame.children);
+
+                if !frame.recursive {
+                    node.total += elapsed;
+                }
+            });
+
+            Some((elapsed, position == 0))
+        });
+
+        // Only the outermost call is reported on its own, nested ones show up
+        // in the span summary instead.
+        if let Some((elapsed, true)) = popped {
+            self.report(elapsed);
+        }
+    }
+}
+
+/// Shortens a `type_name` to the type itself, so methods are labelled
+/// `Type::method` rather than with the full module path.
+pub fn method_label(type_name: &str, method: &str) -> String {
+    let mut short = String::with_capacity(type_name.len());
+    let mut segment_start = 0;
+
+    for (i, c) in type_name.char_indices() {
+        if c == ':' {
+            continue;
+        }
+
+        let previous = type_name[..i].chars().last();
+
+        if previous == Some(':') {
+            // Drop the path before `::`.
+            short.truncate(segment_start);
+        }
+
+        if !(c.is_alphanumeric() || c == '_') {
+            short.push(c);
+            segment_start = short.len();
+        } else {
+            short.push(c);
+        }
+    }
+
+    format!("{}::{}", short, method)
+}
+
+/// Snapshot of the call tree recorded so far.
+pub fn span_summary() -> SpanSummary {
+    with_tree(|tree| SpanSummary {
+        roots: tree.roots.iter().map(|id| tree.snapshot(*id)).collect(),
+    })
+}
+
+/// Forgets every span recorded so far.
+pub fn reset_spans() {
+    with_tree(|tree| *tree = Tree::default());
+}
+
+/// Sends the call tree to the configured sink, meant to be called once at the
+/// end of a run. Does nothing when no timed function has run.
+pub fn report_span_summary() {
+    let summary = span_summary();
+
+    if !summary.is_empty() {
+        sink::record_summary(&summary);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{method_label, SpanNode, SpanSummary};
+
+    fn node(name: &str, children: Vec<SpanNode>) -> SpanNode {
+        SpanNode {
+            name: name.to_string(),
+            calls: 1,
+            total: Duration::from_millis(2),
+            self_time: Duration::from_millis(1),
+            children,
+        }
+    }
+
+    #[test]
+    fn collapsed_paths_are_depth_first() {
+        let summary = SpanSummary {
+            roots: vec![
+                node(
+                    "a",
+                    vec![node("b", vec![node("c", vec![])]), node("d", vec![])],
+                ),
+                node("e", vec![]),
+            ],
+        };
+
+        let paths = summary
+            .collapsed()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect::<Vec<String>>();
+
+        assert_eq!(paths, vec!["a", "a;b", "a;b;c", "a;d", "e"]);
+    }
+
+    #[test]
+    fn method_labels_drop_module_paths() {
+        assert_eq!(
+            method_label("aoc_2024::day10::TopographicMap", "get"),
+            "TopographicMap::get"
+        );
+        assert_eq!(
+            method_label("demo::Wrapper<alloc::vec::Vec<u8>>", "len"),
+            "Wrapper<Vec<u8>>::len"
+        );
+        assert_eq!(
+            method_label("(u8, core::option::Option<i32>)", "f"),
+            "(u8, Option<i32>)::f"
+        );
+        assert_eq!(method_label("Point", "x"), "Point::x");
+    }
+}
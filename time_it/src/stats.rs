@@ -0,0 +1,106 @@
+use std::{fmt::Display, time::Duration};
+
+/// Summary of repeated measurements of the same function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Returns `None` when there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let n = sorted.len();
+        let nanos = sorted.iter().map(|d| d.as_nanos() as f64);
+
+        let mean = nanos.clone().sum::<f64>() / n as f64;
+        let variance = nanos.map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+
+        Some(Self {
+            iterations: n,
+            min: sorted[0],
+            median: percentile(&sorted, 50),
+            mean: Duration::from_nanos(mean.round() as u64),
+            p95: percentile(&sorted, 95),
+            max: sorted[n - 1],
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        })
+    }
+}
+
+/// Nearest-rank percentile of already sorted samples.
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+
+    sorted[rank - 1]
+}
+
+impl Display for Stats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "over {} iterations: min {:.2?}, median {:.2?}, mean {:.2?}, p95 {:.2?}, stddev {:.2?}",
+            self.iterations, self.min, self.median, self.mean, self.p95, self.stddev
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::Stats;
+
+    fn millis(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|v| Duration::from_millis(*v)).collect()
+    }
+
+    #[test]
+    fn no_samples_has_no_stats() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn single_sample() {
+        let stats = Stats::from_samples(&millis(&[5])).unwrap();
+
+        assert_eq!(stats.min, Duration::from_millis(5));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn stats_of_unsorted_samples() {
+        let stats = Stats::from_samples(&millis(&[9, 2, 4, 4, 4, 5, 5, 7])).unwrap();
+
+        assert_eq!(stats.iterations, 8);
+        assert_eq!(stats.min, Duration::from_millis(2));
+        assert_eq!(stats.max, Duration::from_millis(9));
+        assert_eq!(stats.median, Duration::from_millis(4));
+        assert_eq!(stats.mean, Duration::from_millis(5));
+        assert_eq!(stats.p95, Duration::from_millis(9));
+        assert_eq!(stats.stddev, Duration::from_millis(2));
+    }
+
+    #[test]
+    fn p95_of_hundred_samples() {
+        let samples = (1..=100).collect::<Vec<u64>>();
+        let stats = Stats::from_samples(&millis(&samples)).unwrap();
+
+        assert_eq!(stats.p95, Duration::from_millis(95));
+        assert_eq!(stats.median, Duration::from_millis(50));
+    }
+}
@@ -0,0 +1,39 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use time_it::time_it;
+
+static CALLS: AtomicUsize = AtomicUsize::new(0);
+static WARM_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+#[time_it(iterations = 5)]
+fn counted(offset: usize) -> usize {
+    CALLS.fetch_add(1, Ordering::SeqCst) + offset
+}
+
+#[time_it(iterations = 4, warmup = 3)]
+fn warmed_up(values: &[u64]) -> u64 {
+    WARM_CALLS.fetch_add(1, Ordering::SeqCst);
+    values.iter().sum()
+}
+
+#[time_it]
+fn single(value: u32) -> u32 {
+    value * 2
+}
+
+#[test]
+fn runs_body_once_per_iteration_and_returns_the_first_result() {
+    assert_eq!(counted(10), 10);
+    assert_eq!(CALLS.load(Ordering::SeqCst), 5);
+}
+
+#[test]
+fn warmup_runs_are_not_measured_but_still_run() {
+    assert_eq!(warmed_up(&[1, 2, 3]), 6);
+    assert_eq!(WARM_CALLS.load(Ordering::SeqCst), 7);
+}
+
+#[test]
+fn without_arguments_runs_once() {
+    assert_eq!(single(21), 42);
+}
@@ -0,0 +1,7 @@
+#[test]
+fn compile_tests() {
+    let cases = trybuild::TestCases::new();
+
+    cases.pass("tests/ui/pass/*.rs");
+    cases.compile_fail("tests/ui/fail/*.rs");
+}
@@ -0,0 +1,46 @@
+use std::fs;
+
+use time_it::{set_sink, take_measurements, time_it, Sink};
+
+#[time_it]
+fn add(lhs: u32, rhs: u32) -> u32 {
+    lhs + rhs
+}
+
+#[time_it(iterations = 3)]
+fn benchmarked() -> u32 {
+    1
+}
+
+// The sink is global, so everything runs in one test to keep the order fixed.
+#[test]
+fn measurements_go_to_the_configured_sink() {
+    set_sink(Sink::Memory).unwrap();
+
+    add(1, 2);
+    benchmarked();
+
+    let measurements = take_measurements();
+
+    assert_eq!(measurements.len(), 2);
+    assert_eq!(measurements[0].function, "add");
+    assert!(measurements[0].stats.is_none());
+    assert_eq!(measurements[1].function, "benchmarked");
+    assert_eq!(measurements[1].stats.unwrap().iterations, 3);
+    assert!(take_measurements().is_empty());
+
+    let path = std::env::temp_dir().join(format!("time_it-{}.jsonl", std::process::id()));
+    set_sink(Sink::JsonLines(path.clone())).unwrap();
+
+    add(2, 3);
+    add(3, 4);
+
+    let lines = fs::read_to_string(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+
+    assert_eq!(lines.lines().count(), 2);
+    assert!(lines
+        .lines()
+        .all(|line| line.starts_with(r#"{"function":"add","nanos":"#)));
+    assert!(take_measurements().is_empty());
+}
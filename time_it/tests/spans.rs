@@ -0,0 +1,74 @@
+use std::time::Duration;
+
+use time_it::{reset_spans, set_sink, span_summary, take_measurements, time_it, Sink};
+
+#[time_it]
+fn outer(n: u64) -> u64 {
+    inner(n) + inner(n)
+}
+
+#[time_it]
+fn inner(n: u64) -> u64 {
+    if n == 0 {
+        return leaf();
+    }
+
+    inner(n - 1) + 1
+}
+
+#[time_it]
+fn leaf() -> u64 {
+    std::thread::sleep(Duration::from_millis(1));
+    1
+}
+
+// Spans and the sink are global, so everything runs in one test.
+#[test]
+fn nested_calls_are_aggregated_into_a_tree() {
+    set_sink(Sink::Memory).unwrap();
+    reset_spans();
+
+    assert_eq!(outer(3), 8);
+    assert_eq!(outer(3), 8);
+
+    let measurements = take_measurements();
+
+    assert_eq!(measurements.len(), 2);
+    assert!(measurements.iter().all(|m| m.function == "outer"));
+
+    let summary = span_summary();
+
+    assert_eq!(summary.roots.len(), 1);
+
+    let outer = &summary.roots[0];
+    assert_eq!(outer.name, "outer");
+    assert_eq!(outer.calls, 2);
+    assert_eq!(outer.children.len(), 1);
+
+    // Recursion is folded into a single node.
+    let inner = &outer.children[0];
+    assert_eq!(inner.name, "inner");
+    assert_eq!(inner.calls, 16);
+    assert_eq!(inner.children.len(), 1);
+
+    let leaf = &inner.children[0];
+    assert_eq!(leaf.name, "leaf");
+    assert_eq!(leaf.calls, 4);
+    assert!(leaf.total >= Duration::from_millis(4));
+
+    assert!(outer.total >= inner.total);
+    assert!(inner.total >= leaf.total);
+    assert_eq!(
+        inner.total,
+        inner.self_time + leaf.total,
+        "self time of a recursive function excludes only its timed children"
+    );
+
+    let paths = summary
+        .collapsed()
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect::<Vec<String>>();
+
+    assert_eq!(paths, vec!["outer", "outer;inner", "outer;inner;leaf"]);
+}
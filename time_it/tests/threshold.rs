@@ -0,0 +1,35 @@
+use std::{thread, time::Duration};
+
+use time_it::{set_sink, set_threshold, take_measurements, time_it, Sink};
+
+#[time_it]
+fn fast() -> u32 {
+    1
+}
+
+#[time_it]
+fn slow() -> u32 {
+    thread::sleep(Duration::from_millis(20));
+    2
+}
+
+// The threshold is global, so everything runs in one test to keep the order fixed.
+#[test]
+fn only_calls_above_the_threshold_are_reported() {
+    set_sink(Sink::Memory).unwrap();
+    set_threshold(Some(Duration::from_millis(10)));
+
+    fast();
+    slow();
+
+    let measurements = take_measurements();
+
+    assert_eq!(measurements.len(), 1);
+    assert_eq!(measurements[0].function, "slow");
+
+    set_threshold(None);
+
+    fast();
+
+    assert_eq!(take_measurements().len(), 1);
+}
@@ -0,0 +1,8 @@
+use time_it::time_it;
+
+#[time_it(iterations = 10)]
+async fn fetch() -> u32 {
+    1
+}
+
+fn main() {}
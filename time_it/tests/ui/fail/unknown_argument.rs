@@ -0,0 +1,8 @@
+use time_it::time_it;
+
+#[time_it(repeat = 10)]
+fn part_one() -> u32 {
+    1
+}
+
+fn main() {}
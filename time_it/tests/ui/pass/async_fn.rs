@@ -0,0 +1,78 @@
+use std::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+    time::Duration,
+};
+
+use time_it::{set_sink, take_measurements, time_it, Sink};
+
+/// Returns `Pending` once before completing, like a real await point.
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: std::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+
+        std::thread::sleep(Duration::from_millis(2));
+    }
+}
+
+#[time_it]
+async fn fetch(value: u32) -> u32 {
+    YieldOnce(false).await;
+    value + 1
+}
+
+struct Client;
+
+impl Client {
+    #[time_it]
+    async fn get(&self, value: &str) -> Result<usize, String> {
+        YieldOnce(false).await;
+
+        if value.is_empty() {
+            return Err("empty".to_string());
+        }
+
+        Ok(value.len())
+    }
+}
+
+fn main() {
+    set_sink(Sink::Memory).unwrap();
+
+    assert_eq!(block_on(fetch(1)), 2);
+    assert_eq!(block_on(Client.get("abc")), Ok(3));
+    assert!(block_on(Client.get("")).is_err());
+
+    let measurements = take_measurements();
+
+    assert_eq!(measurements.len(), 3);
+    assert_eq!(measurements[0].function, "fetch");
+    assert_eq!(measurements[1].function, "Client::get");
+
+    // The span stays open across the await, so the sleep between polls counts.
+    assert!(measurements
+        .iter()
+        .all(|measurement| measurement.duration >= Duration::from_millis(2)));
+}
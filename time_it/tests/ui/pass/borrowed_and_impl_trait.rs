@@ -0,0 +1,22 @@
+use time_it::time_it;
+
+#[time_it]
+fn first_word(line: &str) -> &str {
+    line.split_whitespace().next().unwrap_or("")
+}
+
+#[time_it]
+fn evens(values: &[u32]) -> impl Iterator<Item = &u32> + '_ {
+    values.iter().filter(|value| *value % 2 == 0)
+}
+
+#[time_it(iterations = 2)]
+fn doubled(values: &[u32]) -> impl Iterator<Item = u32> + '_ {
+    values.iter().map(|value| value * 2)
+}
+
+fn main() {
+    assert_eq!(first_word("hello world"), "hello");
+    assert_eq!(evens(&[1, 2, 3, 4]).count(), 2);
+    assert_eq!(doubled(&[1, 2]).collect::<Vec<u32>>(), vec![2, 4]);
+}
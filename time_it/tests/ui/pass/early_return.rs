@@ -0,0 +1,39 @@
+use std::num::ParseIntError;
+
+use time_it::time_it;
+
+#[derive(Debug)]
+struct ParseError(String);
+
+impl From<ParseIntError> for ParseError {
+    fn from(err: ParseIntError) -> Self {
+        ParseError(err.to_string())
+    }
+}
+
+#[time_it]
+fn parse_all(input: &str) -> Result<Vec<u32>, ParseError> {
+    if input.is_empty() {
+        return Err(ParseError("empty input".to_string()));
+    }
+
+    let mut values = Vec::new();
+
+    for value in input.split(',') {
+        values.push(value.trim().parse()?);
+    }
+
+    Ok(values)
+}
+
+#[time_it(iterations = 3)]
+fn parse_first(input: &str) -> Result<u32, ParseError> {
+    Ok(input.split(',').next().unwrap_or_default().parse()?)
+}
+
+fn main() {
+    assert_eq!(parse_all("1, 2").unwrap(), vec![1, 2]);
+    assert!(parse_all("").is_err());
+    assert!(parse_all("1,x").is_err());
+    assert_eq!(parse_first("4,5").unwrap(), 4);
+}
@@ -0,0 +1,40 @@
+use std::fmt::Display;
+
+use time_it::time_it;
+
+#[time_it]
+fn largest<T: PartialOrd + Copy>(values: &[T]) -> Option<T> {
+    let mut largest = *values.first()?;
+
+    for value in values {
+        if *value > largest {
+            largest = *value;
+        }
+    }
+
+    Some(largest)
+}
+
+#[time_it]
+fn join<'a, I>(parts: I, separator: &str) -> String
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    parts.into_iter().collect::<Vec<&str>>().join(separator)
+}
+
+struct Wrapper<T>(T);
+
+impl<T: Display> Wrapper<T> {
+    #[time_it]
+    fn show(&self) -> String {
+        format!("<{}>", self.0)
+    }
+}
+
+fn main() {
+    assert_eq!(largest(&[3, 9, 2]), Some(9));
+    assert_eq!(largest::<f64>(&[]), None);
+    assert_eq!(join(["a", "b"], ","), "a,b");
+    assert_eq!(Wrapper(5).show(), "<5>");
+}
@@ -0,0 +1,78 @@
+use time_it::{set_sink, take_measurements, time_it, Sink};
+
+struct TopographicMap {
+    heights: Vec<u8>,
+}
+
+#[time_it]
+impl TopographicMap {
+    fn new(heights: Vec<u8>) -> Self {
+        Self { heights }
+    }
+
+    fn highest(&self) -> Option<u8> {
+        self.heights.iter().max().copied()
+    }
+}
+
+struct OrderingRules(Vec<(u32, u32)>);
+
+impl OrderingRules {
+    #[time_it]
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[time_it(label = "rules")]
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+trait Describe {
+    fn describe(&self) -> String;
+
+    #[time_it]
+    fn shout(&self) -> String {
+        self.describe().to_uppercase()
+    }
+}
+
+#[time_it]
+impl Describe for OrderingRules {
+    fn describe(&self) -> String {
+        format!("{} rules", self.0.len())
+    }
+}
+
+fn main() {
+    set_sink(Sink::Memory).unwrap();
+
+    let map = TopographicMap::new(vec![1, 9, 3]);
+    assert_eq!(map.highest(), Some(9));
+
+    let rules = OrderingRules(vec![(1, 2)]);
+    assert_eq!(rules.len(), 1);
+    assert!(!rules.is_empty());
+    assert_eq!(rules.describe(), "1 rules");
+
+    let labels = take_measurements()
+        .into_iter()
+        .map(|measurement| measurement.function)
+        .collect::<Vec<String>>();
+
+    assert_eq!(
+        labels,
+        vec![
+            "TopographicMap::new",
+            "TopographicMap::highest",
+            "OrderingRules::len",
+            "rules",
+            "OrderingRules::describe",
+        ]
+    );
+
+    // Nested in `shout`, so only `shout` is reported on its own.
+    assert_eq!(rules.shout(), "1 RULES");
+    assert_eq!(take_measurements()[0].function, "OrderingRules::shout");
+}